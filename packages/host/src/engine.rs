@@ -1,25 +1,43 @@
 use std::io::{Cursor, Read};
 use cosmwasm_std::{Api, debug_print, Extern, Querier, StdError, Storage};
 use libflate::gzip::Decoder;
+use parity_wasm::elements;
 use wasmi::{Module, ModuleInstance, ModuleRef, RuntimeValue};
 
 use crate::errors::{Wasm2EngineError, wasmi_error_to_wasm2_error};
 use crate::import_resolver::{create_builder, Wasm2ImportResolver};
 use crate::instance::{Wasm2Instance, Wasm2Operation};
+use crate::version::{detect_interface_version, InterfaceVersion};
+
+/// A parsed contract along with the VM interface version it was compiled for
+pub struct Wasm2Module {
+    pub module: Module,
+    pub version: InterfaceVersion,
+}
 
 pub struct Engine<'d, S: Storage, A: Api, Q: Querier> {
     instance: Wasm2Instance<'d, S, A, Q>,
     module: ModuleRef,
+    version: InterfaceVersion,
 }
 
 impl<'d, S: Storage, A: Api, Q: Querier> Engine<'d, S, A, Q> {
-    pub fn new(instance: Wasm2Instance<'d, S, A, Q>, module: ModuleRef) -> Self {
+    pub fn new(
+        instance: Wasm2Instance<'d, S, A, Q>,
+        module: ModuleRef,
+        version: InterfaceVersion,
+    ) -> Self {
         Self {
             instance,
             module,
+            version,
         }
     }
 
+    pub fn interface_version(&self) -> InterfaceVersion {
+        self.version
+    }
+
     pub fn write_to_memory(&mut self, buffer: &[u8]) -> Result<u32, Wasm2EngineError> {
         self.instance.write_to_memory(buffer)
     }
@@ -28,6 +46,41 @@ impl<'d, S: Storage, A: Api, Q: Querier> Engine<'d, S, A, Q> {
         self.instance.extract_vector(vec_ptr_ptr)
     }
 
+    /// Gas charged for the host calls made so far, see the `gas` module
+    pub fn gas_used(&self) -> u64 {
+        self.instance.gas_used()
+    }
+
+    /// init is called once when the contract is instantiated. It is optional, as contracts
+    /// without setup only export handle and query.
+    pub fn init(&mut self, env_ptr: u32, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
+        debug_print!("WASM2[HOST]: Invoking init() in wasm");
+
+        if self.module.export_by_name("init").is_none() {
+            return Err(Wasm2EngineError::MissingExport("init".to_string()));
+        }
+
+        match self
+            .module
+            .invoke_export(
+                "init",
+                &[
+                    RuntimeValue::I32(env_ptr as i32),
+                    RuntimeValue::I32(msg_ptr as i32),
+                ],
+                &mut self.instance,
+            )
+            .map_err(wasmi_error_to_wasm2_error(
+                "error calling 'init' in guest".to_string()))?
+        {
+            Some(RuntimeValue::I32(offset)) => Ok(offset as u32),
+            other => {
+                debug_print!("WASM2[HOST]: init method returned value which wasn't u32: {:?}", other);
+                Err(Wasm2EngineError::Panic)
+            }
+        }
+    }
+
     pub fn handle(&mut self, env_ptr: u32, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
         debug_print!("WASM2[HOST]: Invoking handle() in wasm");
 
@@ -78,14 +131,83 @@ impl<'d, S: Storage, A: Api, Q: Querier> Engine<'d, S, A, Q> {
         }
     }
 
-    pub fn query(&mut self, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
+    /// sudo is optional for contracts, so a missing export is reported as such
+    /// instead of a generic interpreter error.
+    pub fn sudo(&mut self, env_ptr: u32, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
+        debug_print!("WASM2[HOST]: Invoking sudo() in wasm");
+
+        if self.module.export_by_name("sudo").is_none() {
+            return Err(Wasm2EngineError::MissingExport("sudo".to_string()));
+        }
+
+        match self
+            .module
+            .invoke_export(
+                "sudo",
+                &[
+                    RuntimeValue::I32(env_ptr as i32),
+                    RuntimeValue::I32(msg_ptr as i32),
+                ],
+                &mut self.instance,
+            )
+            .map_err(wasmi_error_to_wasm2_error(
+                "error calling 'sudo' in guest".to_string()))?
+        {
+            Some(RuntimeValue::I32(offset)) => Ok(offset as u32),
+            other => {
+                debug_print!("WASM2[HOST]: sudo method returned value which wasn't u32: {:?}", other);
+                Err(Wasm2EngineError::Panic)
+            }
+        }
+    }
+
+    /// reply is optional for contracts, it is only needed by those that send submessages.
+    pub fn reply(&mut self, env_ptr: u32, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
+        debug_print!("WASM2[HOST]: Invoking reply() in wasm");
+
+        if self.module.export_by_name("reply").is_none() {
+            return Err(Wasm2EngineError::MissingExport("reply".to_string()));
+        }
+
+        match self
+            .module
+            .invoke_export(
+                "reply",
+                &[
+                    RuntimeValue::I32(env_ptr as i32),
+                    RuntimeValue::I32(msg_ptr as i32),
+                ],
+                &mut self.instance,
+            )
+            .map_err(wasmi_error_to_wasm2_error(
+                "error calling 'reply' in guest".to_string()))?
+        {
+            Some(RuntimeValue::I32(offset)) => Ok(offset as u32),
+            other => {
+                debug_print!("WASM2[HOST]: reply method returned value which wasn't u32: {:?}", other);
+                Err(Wasm2EngineError::Panic)
+            }
+        }
+    }
+
+    /// env_ptr must be set for contracts whose interface version passes the Env to queries
+    /// (see `InterfaceVersion::query_has_env`), and left empty for older ones.
+    pub fn query(&mut self, env_ptr: Option<u32>, msg_ptr: u32) -> Result<u32, Wasm2EngineError> {
         debug_print!("WASM2[HOST]: Invoking query() in wasm");
 
+        let args = match env_ptr {
+            Some(env_ptr) => vec![
+                RuntimeValue::I32(env_ptr as i32),
+                RuntimeValue::I32(msg_ptr as i32),
+            ],
+            None => vec![RuntimeValue::I32(msg_ptr as i32)],
+        };
+
         match self
             .module
             .invoke_export(
                 "query",
-                &[RuntimeValue::I32(msg_ptr as i32)],
+                &args,
                 &mut self.instance,
             )
             .map_err(wasmi_error_to_wasm2_error(
@@ -118,16 +240,28 @@ pub fn deflate_wasm(compressed_bytes: &[u8]) -> Result<Vec<u8>, StdError> {
     return Ok(buf);
 }
 
-pub fn parse_wasm(wasm_binary_u8: &[u8]) -> Result<Module, StdError> {
-    return match Module::from_buffer(&wasm_binary_u8) {
+pub fn parse_wasm(wasm_binary_u8: &[u8]) -> Result<Wasm2Module, StdError> {
+    let elements_module = parity_wasm::deserialize_buffer::<elements::Module>(&wasm_binary_u8)
+        .map_err(|err| StdError::GenericErr {
+            msg: format!("failed to parse WASM binary: {}", err),
+            backtrace: None,
+        })?;
+
+    // Refuse contracts built for an interface we can't serve before doing any further work.
+    let version = detect_interface_version(&elements_module)?;
+
+    return match Module::from_parity_wasm_module(elements_module) {
         Ok(tree) => {
-            debug_print("WASM: parsed module");
+            debug_print!("WASM: parsed module (interface version {})", version);
 
-            Ok(tree)
+            Ok(Wasm2Module {
+                module: tree,
+                version,
+            })
         }
         Err(err) => {
             Err(StdError::GenericErr {
-                msg: format!("failed to parse WASM binary: {err}"),
+                msg: format!("failed to parse WASM binary: {}", err),
                 backtrace: None,
             })
         }
@@ -147,19 +281,19 @@ pub fn start_engine_from_wasm_binary<'d, S: Storage, A: Api, Q: Querier>(
 
 pub fn start_engine<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
-    module: Module,
+    module: Wasm2Module,
     operation: Wasm2Operation,
 ) -> Result<Engine<S, A, Q>, StdError> {
-    let resolver = Wasm2ImportResolver {};
+    let resolver = Wasm2ImportResolver::new(module.version);
     let imports = create_builder(&resolver);
 
     // Instantiate a module with our imports and assert that there is no `start` function.
-    let module_instance = ModuleInstance::new(&module, &imports)
+    let module_instance = ModuleInstance::new(&module.module, &imports)
         .map_err(|err| {
             debug_print!("Error in instantiation: {:?}", err);
 
             return StdError::GenericErr {
-                msg: format!("WASM2 module invalid: {err}"),
+                msg: format!("WASM2 module invalid: {}", err),
                 backtrace: None,
             };
         })?;
@@ -171,7 +305,188 @@ pub fn start_engine<S: Storage, A: Api, Q: Querier>(
     }
 
     let module_ref = module_instance.not_started_instance().clone();
+    let has_memory = module_ref
+        .export_by_name("memory")
+        .and_then(|export| export.as_memory().cloned())
+        .is_some();
+    if !has_memory {
+        return Err(StdError::GenericErr {
+            msg: "WASM2 module provided should export its 'memory'".to_string(),
+            backtrace: None,
+        });
+    }
+
     let instance = Wasm2Instance::new(deps, module_ref.clone(), operation);
 
-    Ok(Engine::new(instance, module_ref))
+    Ok(Engine::new(instance, module_ref, module.version))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    use cosmwasm_std::testing::mock_dependencies;
+    use libflate::gzip::Encoder;
+
+    use crate::fixtures::{contract, instantiate, module};
+
+    #[test]
+    fn optional_exports_are_reported_missing() {
+        let wasm = contract("", "");
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        for (name, res) in [
+            ("init", engine.init(0, 0)),
+            ("sudo", engine.sudo(0, 0)),
+            ("reply", engine.reply(0, 0)),
+        ] {
+            match res {
+                Err(Wasm2EngineError::MissingExport(export)) => assert_eq!(export, name),
+                other => panic!("unexpected result for {}: {:?}", name, other),
+            }
+        }
+    }
+
+    #[test]
+    fn missing_entry_points_fail() {
+        let wasm = contract("", "");
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert!(matches!(
+            engine.handle(0, 0),
+            Err(Wasm2EngineError::InterpreterError(_))
+        ));
+        assert!(matches!(
+            engine.query(Some(0), 0),
+            Err(Wasm2EngineError::InterpreterError(_))
+        ));
+    }
+
+    #[test]
+    fn missing_memory_is_refused() {
+        let wasm = module(r#"(func (export "wasm2_vm_version_3"))"#);
+        let mut deps = mock_dependencies(20, &[]);
+
+        let err = start_engine(&mut deps, parse_wasm(&wasm).unwrap(), Wasm2Operation::Handle)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            StdError::generic_err("WASM2 module provided should export its 'memory'")
+        );
+    }
+
+    #[test]
+    fn missing_allocate_fails_to_write() {
+        let wasm = module(
+            r#"
+            (memory (export "memory") 1)
+            (func (export "wasm2_vm_version_3"))
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert!(matches!(
+            engine.write_to_memory(b"msg"),
+            Err(Wasm2EngineError::MemoryAllocationError)
+        ));
+    }
+
+    #[test]
+    fn null_allocation_fails_to_write() {
+        let wasm = module(
+            r#"
+            (memory (export "memory") 1)
+            (func (export "wasm2_vm_version_3"))
+            (func (export "allocate") (param i32) (result i32) (i32.const 0))
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert!(matches!(
+            engine.write_to_memory(b"msg"),
+            Err(Wasm2EngineError::MemoryAllocationError)
+        ));
+    }
+
+    #[test]
+    fn start_section_is_refused() {
+        let wasm = contract(
+            "",
+            r#"
+            (func $start)
+            (start $start)
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+
+        let err = start_engine(&mut deps, parse_wasm(&wasm).unwrap(), Wasm2Operation::Handle)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            StdError::generic_err("WASM2 module provided should not have 'start' defined")
+        );
+    }
+
+    #[test]
+    fn unknown_imports_are_refused() {
+        let wasm = contract(r#"(import "env" "db_scan" (func (param i32)))"#, "");
+        let mut deps = mock_dependencies(20, &[]);
+
+        let res = start_engine(&mut deps, parse_wasm(&wasm).unwrap(), Wasm2Operation::Handle);
+        match res.err().unwrap() {
+            StdError::GenericErr { msg, .. } => assert!(msg.starts_with("WASM2 module invalid")),
+            err => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn imports_depend_on_the_interface_version() {
+        // abort was introduced with version 4
+        let wasm = module(
+            r#"
+            (import "env" "abort" (func (param i32)))
+            (memory (export "memory") 1)
+            (func (export "wasm2_vm_version_3"))
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+
+        let res = start_engine(&mut deps, parse_wasm(&wasm).unwrap(), Wasm2Operation::Handle);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_wasm_checks_interface_version() {
+        let unmarked = module(r#"(memory (export "memory") 1)"#);
+        assert!(parse_wasm(&unmarked).is_err());
+
+        let unsupported = module(r#"(func (export "wasm2_vm_version_99"))"#);
+        assert!(parse_wasm(&unsupported).is_err());
+
+        let wasm = contract("", "");
+        assert_eq!(parse_wasm(&wasm).unwrap().version, InterfaceVersion::V4);
+        assert!(parse_wasm(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn start_engine_from_wasm_binary_deflates() {
+        let wasm = contract("", r#"(func (export "handle") (param i32 i32) (result i32) (i32.const 7))"#);
+        let mut encoder = Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(&wasm).unwrap();
+        let compressed = encoder.finish().into_result().unwrap();
+
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine =
+            start_engine_from_wasm_binary(&compressed, &mut deps, Wasm2Operation::Handle)
+                .ok()
+                .unwrap();
+        assert_eq!(engine.handle(0, 0).unwrap(), 7);
+    }
 }
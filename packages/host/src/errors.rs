@@ -1,15 +1,34 @@
 use cosmwasm_std::{debug_print, StdError};
 use derive_more::Display;
 use serde_json_wasm::ser::Error;
-use wasmi::{Error as InterpreterError, HostError};
+use wasmi::{Error as InterpreterError, HostError, TrapKind};
 
-#[derive(Debug, Display)]
+#[derive(Clone, Debug, Display)]
 #[non_exhaustive]
 pub enum Wasm2EngineError {
     HostMisbehavior,
     OutOfGas,
     Panic,
 
+    /// The contract panicked and reported the message through the `abort` import
+    #[display(fmt = "Panic: {}", _0)]
+    ContractPanic(String),
+    /// The contract executed `unreachable` without reporting a panic message
+    Unreachable,
+    MemoryAccessOutOfBounds,
+    /// Usually caused by infinite or very deep recursion in the contract
+    StackOverflow,
+    DivisionByZero,
+    /// Any other trap raised by the interpreter
+    #[display(fmt = "Trap: {}", _0)]
+    Trap(String),
+    /// A host error which was not raised by this engine
+    #[display(fmt = "Host error: {}", _0)]
+    HostError(String),
+    /// An interpreter error that is not a trap, e.g. calling an export with the wrong signature
+    #[display(fmt = "Interpreter error: {}", _0)]
+    InterpreterError(String),
+
     EncryptionError,
     DecryptionError,
     SerializationError,
@@ -24,6 +43,9 @@ pub enum Wasm2EngineError {
     UnauthorizedWrite,
 
     NonExistentImportFunction,
+    /// The contract does not export an optional entry point, e.g. `sudo` or `reply`
+    #[display(fmt = "Contract does not export '{}'", _0)]
+    MissingExport(String),
 }
 
 impl HostError for Wasm2EngineError {}
@@ -33,7 +55,30 @@ pub fn wasmi_error_to_wasm2_error(msg: String) -> impl Fn(InterpreterError) -> W
         debug_print!(
             "WASM2[HOST]: WASMI host error - {}: {}", &msg, err.to_string()
         );
-        Wasm2EngineError::Panic
+        match err {
+            InterpreterError::Trap(trap) => trap_kind_to_wasm2_error(trap.into_kind()),
+            InterpreterError::Host(host_err) => host_error_to_wasm2_error(host_err),
+            other => Wasm2EngineError::InterpreterError(other.to_string()),
+        }
+    }
+}
+
+fn trap_kind_to_wasm2_error(kind: TrapKind) -> Wasm2EngineError {
+    match kind {
+        TrapKind::Unreachable => Wasm2EngineError::Unreachable,
+        TrapKind::MemoryAccessOutOfBounds => Wasm2EngineError::MemoryAccessOutOfBounds,
+        TrapKind::StackOverflow => Wasm2EngineError::StackOverflow,
+        TrapKind::DivisionByZero => Wasm2EngineError::DivisionByZero,
+        TrapKind::Host(host_err) => host_error_to_wasm2_error(host_err),
+        other => Wasm2EngineError::Trap(format!("{:?}", other)),
+    }
+}
+
+/// Host functions trap with a Wasm2EngineError, so we recover it instead of flattening it into a string
+fn host_error_to_wasm2_error(host_err: Box<dyn HostError>) -> Wasm2EngineError {
+    match host_err.downcast_ref::<Wasm2EngineError>() {
+        Some(err) => err.clone(),
+        None => Wasm2EngineError::HostError(host_err.to_string()),
     }
 }
 
@@ -13,6 +13,8 @@ pub enum HostFunctions {
     CanonicalizeAddressIndex = 3,
     HumanizeAddressIndex = 4,
     QueryChainIndex = 6,
+    AbortIndex = 7,
+    AddrValidateIndex = 8,
     #[cfg(feature = "debug-print")]
     DebugPrintIndex = 254,
     Unknown,
@@ -31,6 +33,8 @@ impl From<usize> for HostFunctions {
                 HostFunctions::HumanizeAddressIndex
             }
             x if x == HostFunctions::QueryChainIndex as usize => HostFunctions::QueryChainIndex,
+            x if x == HostFunctions::AbortIndex as usize => HostFunctions::AbortIndex,
+            x if x == HostFunctions::AddrValidateIndex as usize => HostFunctions::AddrValidateIndex,
             #[cfg(feature = "debug-print")]
             x if x == HostFunctions::DebugPrintIndex as usize => HostFunctions::DebugPrintIndex,
             _ => HostFunctions::Unknown,
@@ -118,6 +122,18 @@ impl <'d, S: Storage, A: Api, Q: Querier> Externals for Wasm2Instance<'d, S, A,
 
                 self.humanize_address_index(canonical, human)
             }
+            // fn addr_validate(human: *const c_void) -> i32;
+            HostFunctions::AddrValidateIndex => {
+                let human: i32 = args.nth_checked(0).map_err(|err| {
+                    debug_print!(
+                        "WASM2[HOST]: addr_validate() error reading argument, stopping wasm: {:?}",
+                        err
+                    );
+                    err
+                })?;
+
+                self.addr_validate_index(human)
+            }
             HostFunctions::QueryChainIndex => {
                 let query: i32 = args.nth_checked(0).map_err(|err| {
                     debug_print!(
@@ -129,6 +145,17 @@ impl <'d, S: Storage, A: Api, Q: Querier> Externals for Wasm2Instance<'d, S, A,
 
                 self.query_chain_index(query)
             }
+            HostFunctions::AbortIndex => {
+                let message: i32 = args.nth_checked(0).map_err(|err| {
+                    debug_print!(
+                        "WASM2[HOST]: abort() error reading argument, stopping wasm: {:?}",
+                        err
+                    );
+                    err
+                })?;
+
+                self.abort_index(message)
+            }
             #[cfg(feature = "debug-print")]
             HostFunctions::DebugPrintIndex => {
                 let message: i32 = args.nth_checked(0).map_err(|err| {
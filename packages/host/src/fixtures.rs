@@ -0,0 +1,96 @@
+//! WAT fixtures for the host tests. They are compiled at test time, so no wasm32 toolchain
+//! is needed.
+//!
+//! `contract` wraps the given imports and definitions into a module exporting `memory`, the
+//! interface version marker and a bump `allocate`, like a contract built with wasm2-std.
+//! Static data is placed below the heap with `region` and `buffer`.
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::Extern;
+
+use crate::engine::{parse_wasm, start_engine, Engine};
+use crate::instance::Wasm2Operation;
+
+pub const DB_READ: &str = r#"(import "env" "db_read" (func $db_read (param i32) (result i32)))"#;
+pub const DB_WRITE: &str = r#"(import "env" "db_write" (func $db_write (param i32 i32)))"#;
+pub const DB_REMOVE: &str = r#"(import "env" "db_remove" (func $db_remove (param i32)))"#;
+pub const CANONICALIZE_ADDRESS: &str = r#"(import "env" "canonicalize_address" (func $canonicalize_address (param i32 i32) (result i32)))"#;
+pub const HUMANIZE_ADDRESS: &str =
+    r#"(import "env" "humanize_address" (func $humanize_address (param i32 i32) (result i32)))"#;
+pub const ADDR_VALIDATE: &str =
+    r#"(import "env" "addr_validate" (func $addr_validate (param i32) (result i32)))"#;
+pub const QUERY_CHAIN: &str =
+    r#"(import "env" "query_chain" (func $query_chain (param i32) (result i32)))"#;
+pub const ABORT: &str = r#"(import "env" "abort" (func $abort (param i32)))"#;
+pub const DEBUG_PRINT: &str = r#"(import "env" "debug_print" (func $debug_print (param i32)))"#;
+
+pub type MockDeps = Extern<MockStorage, MockApi, MockQuerier>;
+
+/// Compiles a contract with the given imports, followed by its own definitions
+pub fn contract(imports: &str, definitions: &str) -> Vec<u8> {
+    module(&format!(
+        r#"
+        {imports}
+        (memory (export "memory") 1)
+        (global $heap (mut i32) (i32.const 4096))
+        (func (export "wasm2_vm_version_4"))
+        (func (export "allocate") (param $size i32) (result i32)
+            (local $region i32)
+            (local.set $region (global.get $heap))
+            (i32.store (local.get $region) (i32.add (local.get $region) (i32.const 12)))
+            (i32.store offset=4 (local.get $region) (local.get $size))
+            (i32.store offset=8 (local.get $region) (i32.const 0))
+            (global.set $heap
+                (i32.add (global.get $heap) (i32.add (local.get $size) (i32.const 12))))
+            (local.get $region))
+        (func (export "deallocate") (param i32))
+        {definitions}
+        "#,
+        imports = imports,
+        definitions = definitions,
+    ))
+}
+
+/// Compiles a module from the given fields, for fixtures which are no regular contract
+pub fn module(fields: &str) -> Vec<u8> {
+    wat::parse_str(format!("(module {})", fields)).unwrap()
+}
+
+/// Places a region holding `data` at `offset`, its data follows the 12 bytes of the region
+pub fn region(offset: u32, data: &[u8]) -> String {
+    let len = data.len() as u32;
+    let mut bytes = region_header(offset + 12, len, len);
+    bytes.extend_from_slice(data);
+    data_segment(offset, &bytes)
+}
+
+/// Places an empty region with room for `capacity` bytes at `offset`
+pub fn buffer(offset: u32, capacity: u32) -> String {
+    data_segment(offset, &region_header(offset + 12, capacity, 0))
+}
+
+/// Places a region with arbitrary fields at `offset`, to hand malformed regions to the host
+pub fn raw_region(offset: u32, ptr: u32, capacity: u32, len: u32) -> String {
+    data_segment(offset, &region_header(ptr, capacity, len))
+}
+
+/// Starts an engine on the given contract, bound to `deps`
+pub fn instantiate<'d>(
+    deps: &'d mut MockDeps,
+    wasm: &[u8],
+    operation: Wasm2Operation,
+) -> Engine<'d, MockStorage, MockApi, MockQuerier> {
+    start_engine(deps, parse_wasm(wasm).unwrap(), operation).unwrap()
+}
+
+fn region_header(ptr: u32, capacity: u32, len: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(12);
+    header.extend_from_slice(&ptr.to_le_bytes());
+    header.extend_from_slice(&capacity.to_le_bytes());
+    header.extend_from_slice(&len.to_le_bytes());
+    header
+}
+
+fn data_segment(offset: u32, bytes: &[u8]) -> String {
+    let escaped: String = bytes.iter().map(|byte| format!("\\{:02x}", byte)).collect();
+    format!(r#"(data (i32.const {}) "{}")"#, offset, escaped)
+}
@@ -0,0 +1,16 @@
+//! Storage gas charged by the host functions, with the costs of the KVStore of the Cosmos SDK
+//! (`KVGasConfig`), so the amounts are comparable to what the chain charges for storage access.
+//!
+//! Only storage host calls are metered, the wasm instructions executed by the contract are not.
+//! `query_chain` is not charged either, the querier is responsible for the cost of its queries.
+
+/// Charged for every `db_read`, found or not
+pub const GAS_READ_FLAT: u64 = 1000;
+/// Charged per byte of the key and of the value read
+pub const GAS_READ_PER_BYTE: u64 = 3;
+/// Charged for every `db_write`
+pub const GAS_WRITE_FLAT: u64 = 2000;
+/// Charged per byte of the key and of the value written
+pub const GAS_WRITE_PER_BYTE: u64 = 30;
+/// Charged for every `db_remove`
+pub const GAS_DELETE: u64 = 1000;
@@ -4,6 +4,7 @@ use wasmi::{
 };
 
 use super::externals::HostFunctions;
+use super::version::InterfaceVersion;
 
 pub fn create_builder(resolver: &dyn ModuleImportResolver) -> ImportsBuilder {
     ImportsBuilder::new().with_resolver("env", resolver)
@@ -13,7 +14,16 @@ pub fn create_builder(resolver: &dyn ModuleImportResolver) -> ImportsBuilder {
 /// When instansiating a module we give it this resolver
 /// When invoking a function inside the module we can give it different runtimes (which we probably won't do)
 #[derive(Debug, Clone)]
-pub struct Wasm2ImportResolver {}
+pub struct Wasm2ImportResolver {
+    version: InterfaceVersion,
+}
+
+impl Wasm2ImportResolver {
+    /// Creates a resolver serving the imports of the given interface version
+    pub fn new(version: InterfaceVersion) -> Self {
+        Self { version }
+    }
+}
 
 /// These functions should be available to invoke from wasm code
 /// These should pass the request up to go-cosmwasm:
@@ -28,48 +38,83 @@ impl ModuleImportResolver for Wasm2ImportResolver {
         func_name: &str,
         _signature: &Signature,
     ) -> Result<FuncRef, InterpreterError> {
-        let func_ref = match func_name {
-            // fn read_db(key: *const c_void, value: *mut c_void) -> i32;
-            "db_read" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
-                HostFunctions::ReadDbIndex.into(),
-            ),
-            // fn write_db(key: *const c_void, value: *mut c_void);
-            "db_write" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32, ValueType::I32][..], None),
-                HostFunctions::WriteDbIndex.into(),
-            ),
-            // fn db_remove(key: *const c_void, value: *mut c_void) -> i32;
-            "db_remove" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32][..], None),
-                HostFunctions::RemoveDbIndex.into(),
-            ),
-            // fn canonicalize_address(human: *const c_void, canonical: *mut c_void) -> i32;
-            "canonicalize_address" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32, ValueType::I32][..], Some(ValueType::I32)),
-                HostFunctions::CanonicalizeAddressIndex.into(),
-            ),
-            // fn humanize_address(canonical: *const c_void, human: *mut c_void) -> i32;
-            "humanize_address" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32, ValueType::I32][..], Some(ValueType::I32)),
-                HostFunctions::HumanizeAddressIndex.into(),
-            ),
-            "query_chain" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
-                HostFunctions::QueryChainIndex.into(),
-            ),
-            #[cfg(feature = "debug-print")]
-            "debug_print" => FuncInstance::alloc_host(
-                Signature::new(&[ValueType::I32][..], None),
-                HostFunctions::DebugPrintIndex.into(),
-            ),
-            _ => {
-                return Err(InterpreterError::Function(format!(
-                    "WASM VM doesn't export function with name {}",
-                    func_name
-                )));
-            }
-        };
-        Ok(func_ref)
+        match self.version {
+            // Version 2 only changed the query export, the imports are the same.
+            InterfaceVersion::V1 | InterfaceVersion::V2 => resolve_func_v1(func_name),
+            InterfaceVersion::V3 => resolve_func_v3(func_name),
+            InterfaceVersion::V4 => resolve_func_v4(func_name),
+        }
+    }
+}
+
+/// The imports of interface version 1. A later interface version gets its own resolve function,
+/// which may share the host functions of this one.
+fn resolve_func_v1(func_name: &str) -> Result<FuncRef, InterpreterError> {
+    let func_ref = match func_name {
+        // fn read_db(key: *const c_void, value: *mut c_void) -> i32;
+        "db_read" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+            HostFunctions::ReadDbIndex.into(),
+        ),
+        // fn write_db(key: *const c_void, value: *mut c_void);
+        "db_write" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32, ValueType::I32][..], None),
+            HostFunctions::WriteDbIndex.into(),
+        ),
+        // fn db_remove(key: *const c_void, value: *mut c_void) -> i32;
+        "db_remove" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], None),
+            HostFunctions::RemoveDbIndex.into(),
+        ),
+        // fn canonicalize_address(human: *const c_void, canonical: *mut c_void) -> i32;
+        "canonicalize_address" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32, ValueType::I32][..], Some(ValueType::I32)),
+            HostFunctions::CanonicalizeAddressIndex.into(),
+        ),
+        // fn humanize_address(canonical: *const c_void, human: *mut c_void) -> i32;
+        "humanize_address" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32, ValueType::I32][..], Some(ValueType::I32)),
+            HostFunctions::HumanizeAddressIndex.into(),
+        ),
+        "query_chain" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+            HostFunctions::QueryChainIndex.into(),
+        ),
+        #[cfg(feature = "debug-print")]
+        "debug_print" => FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], None),
+            HostFunctions::DebugPrintIndex.into(),
+        ),
+        _ => {
+            return Err(InterpreterError::Function(format!(
+                "WASM VM doesn't export function with name {}",
+                func_name
+            )));
+        }
+    };
+    Ok(func_ref)
+}
+
+/// The imports of interface version 3: those of version 1 and `addr_validate`.
+fn resolve_func_v3(func_name: &str) -> Result<FuncRef, InterpreterError> {
+    match func_name {
+        // fn addr_validate(human: *const c_void) -> i32;
+        "addr_validate" => Ok(FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+            HostFunctions::AddrValidateIndex.into(),
+        )),
+        _ => resolve_func_v1(func_name),
+    }
+}
+
+/// The imports of interface version 4: those of version 3 and `abort`.
+fn resolve_func_v4(func_name: &str) -> Result<FuncRef, InterpreterError> {
+    match func_name {
+        // fn abort(message: *const c_void);
+        "abort" => Ok(FuncInstance::alloc_host(
+            Signature::new(&[ValueType::I32][..], None),
+            HostFunctions::AbortIndex.into(),
+        )),
+        _ => resolve_func_v3(func_name),
     }
 }
@@ -1,13 +1,22 @@
-use cosmwasm_std::{Api, CanonicalAddr, debug_print, Extern, HumanAddr, Querier, Storage};
+use cosmwasm_std::{Api, CanonicalAddr, debug_print, Extern, HumanAddr, Querier, Storage, to_vec};
 use wasmi::{Error as InterpreterError, MemoryInstance, MemoryRef, ModuleRef, RuntimeValue, Trap};
 
 use crate::errors::Wasm2EngineError;
+use crate::gas::{
+    GAS_DELETE, GAS_READ_FLAT, GAS_READ_PER_BYTE, GAS_WRITE_FLAT, GAS_WRITE_PER_BYTE,
+};
 use crate::traits::Wasm2Api;
 
 #[derive(Clone, Copy, Debug)]
 pub enum Wasm2Operation {
+    /// Instantiation through the optional `init` export
+    Init,
     Handle,
     Query,
+    /// Privileged call made by the runtime through the optional `sudo` export
+    Sudo,
+    /// Result of a submessage passed to the optional `reply` export
+    Reply,
     Verify
 }
 
@@ -30,6 +39,7 @@ pub struct Wasm2Instance<'d, S: Storage, A: Api, Q: Querier> {
     pub memory: MemoryRef,
     pub module: ModuleRef,
     operation: Wasm2Operation,
+    gas_used: u64,
 }
 
 impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Instance<'d, S, A, Q> {
@@ -50,9 +60,18 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Instance<'d, S, A, Q> {
             memory,
             module,
             operation,
+            gas_used: 0,
         }
     }
 
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    fn use_gas(&mut self, gas: u64) {
+        self.gas_used = self.gas_used.saturating_add(gas);
+    }
+
     fn get_memory(&self) -> &MemoryInstance {
         &*self.memory
     }
@@ -189,6 +208,8 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
         );
 
         let value = self.deps.storage.get(state_key_name.as_slice());
+        let value_len = value.as_ref().map_or(0, |value| value.len());
+        self.use_gas(GAS_READ_FLAT + GAS_READ_PER_BYTE * (state_key_name.len() + value_len) as u64);
         let value = match value {
             None => return Ok(Some(RuntimeValue::I32(0))),
             Some(value) => value,
@@ -237,6 +258,7 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
             String::from_utf8_lossy(&state_key_name)
         );
 
+        self.use_gas(GAS_DELETE);
         self.deps.storage.remove(state_key_name.as_slice());
 
         Ok(None)
@@ -288,6 +310,7 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
             String::from_utf8_lossy(&value),
         );
 
+        self.use_gas(GAS_WRITE_FLAT + GAS_WRITE_PER_BYTE * (state_key_name.len() + value.len()) as u64);
         self.deps.storage.set(state_key_name.as_slice(), value.as_slice());
 
         Ok(None)
@@ -402,10 +425,60 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
         Ok(Some(RuntimeValue::I32(0)))
     }
 
-    // stub, for now
+    /// Args:
+    /// 1. "human" address to validate (string)
+    /// It is a pointer to a region "struct" of "pointer" and "length"
+    /// Returns 0 if the address is valid, otherwise a pointer to a region holding the error message,
+    /// so the contract can handle invalid user input.
+    fn addr_validate_index(&mut self, human_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap> {
+        let human = self.extract_vector(human_ptr_ptr as u32).map_err(|err| {
+            debug_print!(
+                "WASM2[HOST]: addr_validate() error while trying to read human address from wasm memory"
+            );
+            err
+        })?;
+
+        debug_print!(
+            "WASM2[HOST]: addr_validate() was called from WASM code with {:?}",
+            String::from_utf8_lossy(&human)
+        );
+
+        let human_addr_str = match std::str::from_utf8(&human) {
+            Err(err) => {
+                debug_print!(
+                    "WASM2[HOST]: addr_validate() error while trying to parse human address from bytes to string: {:?}",
+                    err
+                );
+                return Ok(Some(RuntimeValue::I32(
+                    self.write_to_memory(b"input is not valid UTF-8")? as i32,
+                )));
+            }
+            Ok(x) => x,
+        };
+
+        if let Err(err) = self.deps.api.addr_validate(human_addr_str) {
+            debug_print!("WASM2[HOST]: addr_validate() error {:?}", err);
+            return Ok(Some(RuntimeValue::I32(
+                self.write_to_memory(err.to_string().as_bytes())? as i32,
+            )));
+        }
+
+        // return 0 == ok
+        Ok(Some(RuntimeValue::I32(0)))
+    }
+
+    /// Args:
+    /// 1. "query" the serialized QueryRequest to pass to the querier (buffer of bytes)
+    /// It is a pointer to a region "struct" of "pointer" and "length"
+    /// Returns a pointer to a region holding the serialized QuerierResult, so the contract
+    /// can handle system and query errors itself.
+    ///
+    /// No gas is charged here. The `gas` module only prices storage access, and the cost of
+    /// answering a query depends on the querier: it is up to the `Querier` implementation
+    /// to charge for the queries it answers, e.g. by metering a nested contract query.
     fn query_chain_index(&mut self, query_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap> {
         let query_buffer = self.extract_vector(query_ptr_ptr as u32).map_err(|err| {
-            debug_print!("WASM2[HOST]: query_chain() error while trying to read canonical address from wasm memory",);
+            debug_print!("WASM2[HOST]: query_chain() error while trying to read query from wasm memory",);
             err
         })?;
 
@@ -414,16 +487,13 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
             String::from_utf8_lossy(&query_buffer)
         );
 
-        unimplemented!("query_chain_index is not implemented.");
-
-        // TODO:
-        //self.deps.querier.query();
-
-        /*
+        let answer = to_vec(&self.deps.querier.raw_query(&query_buffer)).map_err(|err| {
+            debug_print!("WASM2[HOST]: query_chain() error while trying to serialize the answer: {:?}", err);
+            Wasm2EngineError::SerializationError
+        })?;
 
         debug_print!(
-            "WASM2[HOST]: query_chain() got answer from outside with gas {} and result {:?}",
-            gas_used,
+            "WASM2[HOST]: query_chain() got answer {:?}",
             String::from_utf8_lossy(&answer)
         );
 
@@ -437,7 +507,22 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
 
         // Return pointer to the allocated buffer with the value written to it
         Ok(Some(RuntimeValue::I32(ptr_to_region_in_wasm_vm as i32)))
-         */
+    }
+
+    /// Args:
+    /// 1. "message" the panic message reported by the guest panic hook (string)
+    /// Always traps, so the contract execution stops here.
+    fn abort_index(&self, message_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap> {
+        let message_buffer = self.extract_vector(message_ptr_ptr as u32).map_err(|err| {
+            debug_print!("WASM2[HOST]: abort() error while trying to read message from wasm memory",);
+            err
+        })?;
+
+        let message = String::from_utf8_lossy(&message_buffer).into_owned();
+
+        debug_print!("WASM2[HOST]: abort() was called from WASM code with {:?}", message);
+
+        Err(Wasm2EngineError::ContractPanic(message).into())
     }
 
     #[cfg(feature = "debug-print")]
@@ -464,3 +549,448 @@ impl<'d, S: Storage, A: Api, Q: Querier> Wasm2Api for Wasm2Instance<'d, S, A, Q>
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, MockDependenciesBuilder};
+    use cosmwasm_std::{
+        coins, from_binary, from_slice, BalanceResponse, BankQuery, Empty, QuerierResult,
+        QueryRequest, ReadonlyStorage,
+    };
+
+    use crate::fixtures::{
+        buffer, contract, instantiate, raw_region, region, ABORT, ADDR_VALIDATE,
+        CANONICALIZE_ADDRESS, DB_READ, DB_REMOVE, DB_WRITE, HUMANIZE_ADDRESS, QUERY_CHAIN,
+    };
+
+    /// A contract whose `handle` runs the given body, with the key region "foo" at 1024
+    /// and the value region "bar" at 1280
+    fn handle_contract(imports: &str, body: &str) -> Vec<u8> {
+        contract(
+            imports,
+            &format!(
+                r#"
+                {}
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    {})
+                "#,
+                region(1024, b"foo"),
+                region(1280, b"bar"),
+                body
+            ),
+        )
+    }
+
+    /// Like `handle_contract`, but the body runs in the `query` export
+    fn query_contract(imports: &str, body: &str) -> Vec<u8> {
+        contract(
+            imports,
+            &format!(
+                r#"
+                {}
+                {}
+                (func (export "query") (param i32 i32) (result i32)
+                    {})
+                "#,
+                region(1024, b"foo"),
+                region(1280, b"bar"),
+                body
+            ),
+        )
+    }
+
+    #[test]
+    fn db_write_and_db_read_work() {
+        let wasm = handle_contract(
+            &[DB_READ, DB_WRITE].concat(),
+            r#"
+            (call $db_write (i32.const 1024) (i32.const 1280))
+            (call $db_read (i32.const 1024))
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        let value_ptr = engine.handle(0, 0).unwrap();
+        assert_eq!(engine.extract_vector(value_ptr).unwrap(), b"bar".to_vec());
+        assert_eq!(
+            engine.gas_used(),
+            GAS_WRITE_FLAT + GAS_WRITE_PER_BYTE * 6 + GAS_READ_FLAT + GAS_READ_PER_BYTE * 6
+        );
+        drop(engine);
+
+        assert_eq!(deps.storage.get(b"foo"), Some(b"bar".to_vec()));
+    }
+
+    #[test]
+    fn db_read_returns_null_for_missing_keys() {
+        let wasm = handle_contract(DB_READ, "(call $db_read (i32.const 1024))");
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert_eq!(engine.handle(0, 0).unwrap(), 0);
+        assert_eq!(engine.gas_used(), GAS_READ_FLAT + GAS_READ_PER_BYTE * 3);
+    }
+
+    #[test]
+    fn db_remove_works() {
+        let wasm = handle_contract(
+            DB_REMOVE,
+            r#"
+            (call $db_remove (i32.const 1024))
+            (i32.const 0)
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        deps.storage.set(b"foo", b"bar");
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        engine.handle(0, 0).unwrap();
+        assert_eq!(engine.gas_used(), GAS_DELETE);
+        drop(engine);
+
+        assert_eq!(deps.storage.get(b"foo"), None);
+    }
+
+    #[test]
+    fn query_may_read() {
+        let wasm = query_contract(DB_READ, "(call $db_read (i32.const 1024))");
+        let mut deps = mock_dependencies(20, &[]);
+        deps.storage.set(b"foo", b"bar");
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Query);
+
+        let value_ptr = engine.query(Some(0), 0).unwrap();
+        assert_eq!(engine.extract_vector(value_ptr).unwrap(), b"bar".to_vec());
+    }
+
+    #[test]
+    fn query_may_not_write() {
+        let wasm = query_contract(
+            DB_WRITE,
+            r#"
+            (call $db_write (i32.const 1024) (i32.const 1280))
+            (i32.const 0)
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Query);
+
+        assert!(matches!(
+            engine.query(Some(0), 0),
+            Err(Wasm2EngineError::UnauthorizedWrite)
+        ));
+        drop(engine);
+
+        assert_eq!(deps.storage.get(b"foo"), None);
+    }
+
+    #[test]
+    fn query_may_not_remove() {
+        let wasm = query_contract(
+            DB_REMOVE,
+            r#"
+            (call $db_remove (i32.const 1024))
+            (i32.const 0)
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        deps.storage.set(b"foo", b"bar");
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Query);
+
+        assert!(matches!(
+            engine.query(Some(0), 0),
+            Err(Wasm2EngineError::UnauthorizedWrite)
+        ));
+        drop(engine);
+
+        assert_eq!(deps.storage.get(b"foo"), Some(b"bar".to_vec()));
+    }
+
+    #[test]
+    fn other_operations_may_write() {
+        let wasm = contract(
+            DB_WRITE,
+            &format!(
+                r#"
+                {}
+                {}
+                (func (export "sudo") (param i32 i32) (result i32)
+                    (call $db_write (i32.const 1024) (i32.const 1280))
+                    (i32.const 0))
+                "#,
+                region(1024, b"foo"),
+                region(1280, b"bar"),
+            ),
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Sudo);
+
+        engine.sudo(0, 0).unwrap();
+        drop(engine);
+
+        assert_eq!(deps.storage.get(b"foo"), Some(b"bar".to_vec()));
+    }
+
+    #[test]
+    fn canonicalize_and_humanize_address_work() {
+        let wasm = contract(
+            &[CANONICALIZE_ADDRESS, HUMANIZE_ADDRESS].concat(),
+            &format!(
+                r#"
+                {}
+                {}
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (if (call $canonicalize_address (i32.const 1024) (i32.const 1536))
+                        (then unreachable))
+                    (call $humanize_address (i32.const 1536) (i32.const 2048)))
+                "#,
+                region(1024, b"addr0001"),
+                buffer(1536, 64),
+                buffer(2048, 64),
+            ),
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let canonical = deps
+            .api
+            .canonical_address(&HumanAddr::from("addr0001"))
+            .unwrap();
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert_eq!(engine.handle(0, 0).unwrap(), 0);
+        assert_eq!(engine.extract_vector(1536).unwrap(), canonical.as_slice());
+        assert_eq!(engine.extract_vector(2048).unwrap(), b"addr0001".to_vec());
+    }
+
+    #[test]
+    fn canonicalize_address_rejects_invalid_input() {
+        let imports = CANONICALIZE_ADDRESS;
+        let body = "(call $canonicalize_address (i32.const 1024) (i32.const 1536))";
+        let mut deps = mock_dependencies(20, &[]);
+
+        // not UTF-8, the error is returned to the contract
+        let wasm = contract(
+            imports,
+            &format!(
+                r#"{} {} (func (export "handle") (param i32 i32) (result i32) {})"#,
+                region(1024, &[0xff, 0xfe, 0xfd]),
+                buffer(1536, 64),
+                body
+            ),
+        );
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+        let error_ptr = engine.handle(0, 0).unwrap();
+        assert_eq!(
+            engine.extract_vector(error_ptr).unwrap(),
+            b"input is not valid UTF-8".to_vec()
+        );
+        drop(engine);
+
+        // longer than the canonical length of the Api
+        let wasm = contract(
+            imports,
+            &format!(
+                r#"{} {} (func (export "handle") (param i32 i32) (result i32) {})"#,
+                region(1024, b"addr000000000000000000001"),
+                buffer(1536, 64),
+                body
+            ),
+        );
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+        assert!(matches!(engine.handle(0, 0), Err(Wasm2EngineError::Panic)));
+        drop(engine);
+
+        // the destination buffer is too small
+        let wasm = contract(
+            imports,
+            &format!(
+                r#"{} {} (func (export "handle") (param i32 i32) (result i32) {})"#,
+                region(1024, b"addr0001"),
+                buffer(1536, 4),
+                body
+            ),
+        );
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+        assert!(matches!(
+            engine.handle(0, 0),
+            Err(Wasm2EngineError::MemoryWriteError)
+        ));
+    }
+
+    #[test]
+    fn addr_validate_works() {
+        let wasm = contract(
+            ADDR_VALIDATE,
+            &format!(
+                r#"
+                {}
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (if (call $addr_validate (i32.const 1024))
+                        (then unreachable))
+                    (call $addr_validate (i32.const 1280)))
+                "#,
+                region(1024, b"addr0001"),
+                region(1280, b"ad"),
+            ),
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let expected = deps.api.addr_validate("ad").unwrap_err().to_string();
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        let error_ptr = engine.handle(0, 0).unwrap();
+        assert_ne!(error_ptr, 0);
+        assert_eq!(
+            engine.extract_vector(error_ptr).unwrap(),
+            expected.as_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn query_chain_works() {
+        let request = cosmwasm_std::to_vec(&QueryRequest::<Empty>::Bank(BankQuery::Balance {
+            address: HumanAddr::from("addr0001"),
+            denom: "earth".to_string(),
+        }))
+        .unwrap();
+        let wasm = contract(
+            QUERY_CHAIN,
+            &format!(
+                r#"
+                {}
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (call $query_chain (i32.const 1024)))
+                (func (export "query") (param i32 i32) (result i32)
+                    (call $query_chain (i32.const 512)))
+                "#,
+                region(512, b"not a query"),
+                region(1024, &request),
+            ),
+        );
+        let mut deps = MockDependenciesBuilder::new()
+            .balance("addr0001", &coins(123, "earth"))
+            .build();
+
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+        let answer_ptr = engine.handle(0, 0).unwrap();
+        let answer: QuerierResult = from_slice(&engine.extract_vector(answer_ptr).unwrap()).unwrap();
+        let balance: BalanceResponse = from_binary(&answer.unwrap().unwrap()).unwrap();
+        assert_eq!(balance.amount, coins(123, "earth")[0]);
+        drop(engine);
+
+        // queries can query the chain as well, invalid requests are reported to the contract
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Query);
+        let answer_ptr = engine.query(Some(0), 0).unwrap();
+        let answer: QuerierResult = from_slice(&engine.extract_vector(answer_ptr).unwrap()).unwrap();
+        assert!(answer.is_err());
+    }
+
+    #[test]
+    fn abort_reports_the_panic_message() {
+        let wasm = contract(
+            ABORT,
+            &format!(
+                r#"
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (call $abort (i32.const 1024))
+                    (i32.const 0))
+                "#,
+                region(1024, b"oh no"),
+            ),
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        match engine.handle(0, 0) {
+            Err(Wasm2EngineError::ContractPanic(message)) => assert_eq!(message, "oh no"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "debug-print")]
+    #[test]
+    fn debug_print_works() {
+        let wasm = contract(
+            crate::fixtures::DEBUG_PRINT,
+            &format!(
+                r#"
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (call $debug_print (i32.const 1024))
+                    (i32.const 0))
+                "#,
+                region(1024, b"hello"),
+            ),
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+        assert_eq!(engine.handle(0, 0).unwrap(), 0);
+    }
+
+    #[cfg(not(feature = "debug-print"))]
+    #[test]
+    fn debug_print_is_only_served_with_the_feature() {
+        let wasm = contract(crate::fixtures::DEBUG_PRINT, "");
+        let mut deps = mock_dependencies(20, &[]);
+
+        let module = crate::engine::parse_wasm(&wasm).unwrap();
+        let res = crate::engine::start_engine(&mut deps, module, Wasm2Operation::Handle);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn malformed_regions_are_refused() {
+        for (name, region) in [
+            ("null data pointer", raw_region(1024, 0, 3, 3)),
+            ("data beyond memory", raw_region(1024, 65534, 3, 3)),
+            ("length beyond memory", raw_region(1024, 1036, 3, 0x10_0000)),
+        ] {
+            let wasm = contract(
+                DB_READ,
+                &format!(
+                    r#"
+                    {}
+                    (func (export "handle") (param i32 i32) (result i32)
+                        (call $db_read (i32.const 1024)))
+                    "#,
+                    region
+                ),
+            );
+            let mut deps = mock_dependencies(20, &[]);
+            let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+
+            assert!(
+                matches!(
+                    engine.handle(0, 0),
+                    Err(Wasm2EngineError::MemoryReadError)
+                ),
+                "{} must be refused",
+                name
+            );
+        }
+
+        // the region itself lies beyond the memory
+        let wasm = contract(
+            DB_READ,
+            r#"
+            (func (export "handle") (param i32 i32) (result i32)
+                (call $db_read (i32.const 70000)))
+            "#,
+        );
+        let mut deps = mock_dependencies(20, &[]);
+        let mut engine = instantiate(&mut deps, &wasm, Wasm2Operation::Handle);
+        assert!(matches!(
+            engine.handle(0, 0),
+            Err(Wasm2EngineError::MemoryReadError)
+        ));
+        assert!(matches!(
+            engine.extract_vector(70000),
+            Err(Wasm2EngineError::MemoryReadError)
+        ));
+    }
+}
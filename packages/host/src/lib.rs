@@ -1,4 +1,5 @@
 extern crate libflate;
+extern crate parity_wasm;
 extern crate wasmi;
 
 pub mod engine;
@@ -6,10 +7,21 @@ pub mod instance;
 pub mod externals;
 pub mod import_resolver;
 pub mod errors;
+pub mod gas;
 pub mod traits;
 pub mod operations;
+pub mod version;
+// Test harness for contract builds, kept out of the chain binary
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-pub use engine::{Engine, deflate_wasm, parse_wasm, start_engine_from_wasm_binary, start_engine};
+#[cfg(test)]
+mod fixtures;
+
+pub use engine::{Engine, Wasm2Module, deflate_wasm, parse_wasm, start_engine_from_wasm_binary, start_engine};
 pub use instance::{Wasm2Instance, Wasm2Operation};
 pub use errors::Wasm2EngineError;
-pub use operations::handle;
+pub use operations::{handle, query, reply, sudo};
+pub use version::InterfaceVersion;
+// Ready-made Api converting bech32 addresses, the same codec as MockApi::new_bech32
+pub use cosmwasm_std::Bech32Api;
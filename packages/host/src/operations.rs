@@ -1,4 +1,7 @@
-use cosmwasm_std::{Api, debug_print, Env, Extern, HandleResponse, Querier, StdResult, Storage};
+use cosmwasm_std::{
+    Api, debug_print, Env, Extern, from_slice, HandleResponse, HandleResult, HumanAddr,
+    plaintext_log, Querier, QueryResponse, QueryResult, Reply, StdResult, Storage, SudoMsg,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{start_engine_from_wasm_binary, Wasm2Operation};
@@ -45,5 +48,188 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
 
     debug_print!("WASM2[HOST]: handle call successful, bytes {} returned", res_vec.len());
 
-    Ok(HandleResponse::default())
-}
\ No newline at end of file
+    let res: HandleResult = from_slice(&res_vec)?;
+    res.map(|res| group_events(res, &env.contract.address))
+}
+
+pub fn query<S: Storage, A: Api, Q: Querier>(
+    data: &[u8],
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    msg: &[u8],
+) -> StdResult<QueryResponse> {
+    let mut engine = start_engine_from_wasm_binary(&data, deps,
+                                                   Wasm2Operation::Query)?;
+
+    // Contracts built for interface version 1 export `query(msg_ptr)` and never see the Env.
+    let env_ptr = if engine.interface_version().query_has_env() {
+        let env_bytes = serde_json_wasm::to_vec(&env)
+            .map_err(serde_error_to_stderr(
+                "got an error while trying to serialize 'Env' into bytes".to_string()))?;
+
+        Some(engine.write_to_memory(&env_bytes)
+            .map_err(wasm2_error_to_stderr(
+                "failed to write 'Env' to memory for WASM2 guest".to_string()))?)
+    } else {
+        None
+    };
+
+    let msg_ptr = engine.write_to_memory(msg)
+        .map_err(wasm2_error_to_stderr(
+            "failed to write 'QueryMsg' to memory for WASM2 guest".to_string()))?;
+
+    let res_vec_ptr = engine.query(env_ptr, msg_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error while calling 'query' on WASM2 guest".to_string()))?;
+
+    let res_vec = engine.extract_vector(res_vec_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error extracting the results vector during 'query'".to_string()))?;
+
+    debug_print!("WASM2[HOST]: query call successful, bytes {} returned", res_vec.len());
+
+    let res: QueryResult = from_slice(&res_vec)?;
+    res
+}
+
+/// sudo calls the privileged `sudo` export of a contract, this is never triggered by a user.
+/// Contracts that don't export it fail with `Wasm2EngineError::MissingExport`.
+pub fn sudo<S: Storage, A: Api, Q: Querier, T: Serialize>(
+    data: &[u8],
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    msg: &SudoMsg<T>,
+) -> StdResult<HandleResponse>
+where
+    T: Clone + std::fmt::Debug + PartialEq,
+{
+    let mut engine = start_engine_from_wasm_binary(&data, deps,
+                                                   Wasm2Operation::Sudo)?;
+
+    let env_bytes = serde_json_wasm::to_vec(&env)
+        .map_err(serde_error_to_stderr(
+            "got an error while trying to serialize 'Env' into bytes".to_string()))?;
+    let msg_bytes = serde_json_wasm::to_vec(msg)
+        .map_err(serde_error_to_stderr(
+            "got an error while trying to serialize 'SudoMsg' into bytes".to_string()))?;
+
+    let env_ptr = engine.write_to_memory(&env_bytes)
+        .map_err(wasm2_error_to_stderr(
+            "failed to write 'Env' to memory for WASM2 guest".to_string()))?;
+    let msg_ptr = engine.write_to_memory(&msg_bytes)
+        .map_err(wasm2_error_to_stderr(
+            "failed to write 'SudoMsg' to memory for WASM2 guest".to_string()))?;
+
+    let res_vec_ptr = engine.sudo(env_ptr, msg_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error while calling 'sudo' on WASM2 guest".to_string()))?;
+
+    let res_vec = engine.extract_vector(res_vec_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error extracting the results vector during 'sudo'".to_string()))?;
+
+    debug_print!("WASM2[HOST]: sudo call successful, bytes {} returned", res_vec.len());
+
+    let res: HandleResult = from_slice(&res_vec)?;
+    res.map(|res| group_events(res, &env.contract.address))
+}
+
+/// reply hands the result of a submessage back to the contract that sent it.
+/// Contracts that don't export `reply` fail with `Wasm2EngineError::MissingExport`.
+pub fn reply<S: Storage, A: Api, Q: Querier>(
+    data: &[u8],
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    reply: &Reply,
+) -> StdResult<HandleResponse> {
+    let mut engine = start_engine_from_wasm_binary(&data, deps,
+                                                   Wasm2Operation::Reply)?;
+
+    let env_bytes = serde_json_wasm::to_vec(&env)
+        .map_err(serde_error_to_stderr(
+            "got an error while trying to serialize 'Env' into bytes".to_string()))?;
+    let msg_bytes = serde_json_wasm::to_vec(reply)
+        .map_err(serde_error_to_stderr(
+            "got an error while trying to serialize 'Reply' into bytes".to_string()))?;
+
+    let env_ptr = engine.write_to_memory(&env_bytes)
+        .map_err(wasm2_error_to_stderr(
+            "failed to write 'Env' to memory for WASM2 guest".to_string()))?;
+    let msg_ptr = engine.write_to_memory(&msg_bytes)
+        .map_err(wasm2_error_to_stderr(
+            "failed to write 'Reply' to memory for WASM2 guest".to_string()))?;
+
+    let res_vec_ptr = engine.reply(env_ptr, msg_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error while calling 'reply' on WASM2 guest".to_string()))?;
+
+    let res_vec = engine.extract_vector(res_vec_ptr)
+        .map_err(wasm2_error_to_stderr(
+            "got an error extracting the results vector during 'reply'".to_string()))?;
+
+    debug_print!("WASM2[HOST]: reply call successful, bytes {} returned", res_vec.len());
+
+    let res: HandleResult = from_slice(&res_vec)?;
+    res.map(|res| group_events(res, &env.contract.address))
+}
+
+/// group_events marks the events of a response as emitted by this contract call, like x/wasm does:
+/// their type is prefixed with "wasm-" and a plaintext `_contract_address` attribute comes first.
+/// This keeps the events of a submessage apart from those of the contract that sent it.
+pub(crate) fn group_events<T>(mut response: HandleResponse<T>, contract: &HumanAddr) -> HandleResponse<T>
+where
+    T: Clone + std::fmt::Debug + PartialEq,
+{
+    for event in response.events.iter_mut() {
+        event.ty = format!("wasm-{}", event.ty);
+        event.attributes.insert(0, plaintext_log("_contract_address", contract));
+    }
+    response
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{log, to_vec, Event};
+    use libflate::gzip::Encoder;
+
+    use crate::fixtures::{contract, region};
+
+    #[test]
+    fn handle_groups_the_events_of_the_response() {
+        let mut response = HandleResponse::default();
+        response.events.push(Event::new("transfer").add_attribute("amount", "7"));
+        let res: HandleResult = Ok(response);
+        let wasm = contract(
+            "",
+            &format!(
+                r#"
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (i32.const 1024))
+                "#,
+                region(1024, &to_vec(&res).unwrap()),
+            ),
+        );
+        let mut encoder = Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(&wasm).unwrap();
+        let compressed = encoder.finish().into_result().unwrap();
+
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("creator", &[]);
+        let res = handle(&compressed, &mut deps, env.clone()).unwrap();
+
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].ty, "wasm-transfer");
+        assert_eq!(
+            res.events[0].attributes,
+            vec![
+                plaintext_log("_contract_address", &env.contract.address),
+                log("amount", "7"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! testing runs a compiled contract against the mock dependencies of wasm2-std, through the
+//! same engine the chain uses. Running the same scenario against the native contract and
+//! comparing the results catches differences between both builds.
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, from_slice, to_vec, Empty, Env, Extern, HandleResponse, HandleResult, QueryResult,
+    StdError, StdResult,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::engine::{deflate_wasm, parse_wasm, start_engine};
+use crate::errors::{serde_error_to_stderr, wasm2_error_to_stderr};
+use crate::instance::Wasm2Operation;
+use crate::operations::group_events;
+
+/// Compressed contracts start with the gzip magic bytes, raw ones with "\0asm"
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+pub type MockDeps<C> = Extern<MockStorage, MockApi, MockQuerier<C>>;
+
+/// Execution is the outcome of a call into the contract
+#[derive(Debug)]
+pub struct Execution<T> {
+    pub result: StdResult<T>,
+    /// Gas charged for storage access, see the `gas` module. The wasm instructions executed by
+    /// the contract are not metered, so this is not the full gas cost of the call.
+    pub storage_gas_used: u64,
+    /// Storage entries changed by the call, sorted by key. Failed calls are reverted like on
+    /// chain, so they leave no changes.
+    pub storage_diff: Vec<StorageChange>,
+}
+
+/// StorageChange is a storage entry changed by a call, `None` meaning the key is not set
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageChange {
+    pub key: Vec<u8>,
+    pub old: Option<Vec<u8>>,
+    pub new: Option<Vec<u8>>,
+}
+
+/// WasmHarness runs a compiled contract against mock dependencies, which can be prepared and
+/// inspected through `deps` between calls.
+pub struct WasmHarness<C: DeserializeOwned = Empty> {
+    wasm: Vec<u8>,
+    pub deps: MockDeps<C>,
+}
+
+impl WasmHarness {
+    /// new loads a gzip compressed or raw wasm binary, bound to `mock_dependencies`
+    pub fn new(wasm: &[u8]) -> StdResult<Self> {
+        Self::with_deps(wasm, mock_dependencies(20, &[]))
+    }
+}
+
+impl<C: DeserializeOwned> WasmHarness<C> {
+    /// with_deps loads a gzip compressed or raw wasm binary, bound to the given dependencies
+    pub fn with_deps(wasm: &[u8], deps: MockDeps<C>) -> StdResult<Self> {
+        let wasm = if wasm.starts_with(GZIP_MAGIC) {
+            deflate_wasm(wasm)?
+        } else {
+            wasm.to_vec()
+        };
+        // Refuse invalid binaries right away, the module is parsed again for every call
+        // as instances can't be reset.
+        parse_wasm(&wasm)?;
+
+        Ok(WasmHarness { wasm, deps })
+    }
+
+    /// init calls the optional `init` export
+    pub fn init<M: Serialize>(&mut self, env: Env, msg: &M) -> Execution<HandleResponse> {
+        let contract = env.contract.address.clone();
+        self.call(Wasm2Operation::Init, &env, msg, |res| {
+            let res: HandleResult = from_slice(res)?;
+            res.map(|res| group_events(res, &contract))
+        })
+    }
+
+    pub fn handle<M: Serialize>(&mut self, env: Env, msg: &M) -> Execution<HandleResponse> {
+        let contract = env.contract.address.clone();
+        self.call(Wasm2Operation::Handle, &env, msg, |res| {
+            let res: HandleResult = from_slice(res)?;
+            res.map(|res| group_events(res, &contract))
+        })
+    }
+
+    /// query decodes the response of the contract into `T`
+    pub fn query<M: Serialize, T: DeserializeOwned>(&mut self, env: Env, msg: &M) -> Execution<T> {
+        self.call(Wasm2Operation::Query, &env, msg, |res| {
+            let res: QueryResult = from_slice(res)?;
+            from_binary(&res?)
+        })
+    }
+
+    fn call<M: Serialize, T, F>(
+        &mut self,
+        operation: Wasm2Operation,
+        env: &Env,
+        msg: &M,
+        decode: F,
+    ) -> Execution<T>
+    where
+        F: FnOnce(&[u8]) -> StdResult<T>,
+    {
+        let before = self.deps.storage.clone();
+        let mut storage_gas_used = 0;
+        let result = self
+            .invoke(operation, env, msg, &mut storage_gas_used)
+            .and_then(|res| decode(&res));
+
+        let storage_diff = if result.is_ok() {
+            storage_diff(&before, &self.deps.storage)
+        } else {
+            self.deps.storage = before;
+            vec![]
+        };
+
+        Execution {
+            result,
+            storage_gas_used,
+            storage_diff,
+        }
+    }
+
+    fn invoke<M: Serialize>(
+        &mut self,
+        operation: Wasm2Operation,
+        env: &Env,
+        msg: &M,
+        storage_gas_used: &mut u64,
+    ) -> StdResult<Vec<u8>> {
+        let module = parse_wasm(&self.wasm)?;
+        let mut engine = start_engine(&mut self.deps, module, operation)?;
+
+        let env_bytes = serde_json_wasm::to_vec(env).map_err(serde_error_to_stderr(
+            "got an error while trying to serialize 'Env' into bytes".to_string(),
+        ))?;
+        let msg_bytes = to_vec(msg)?;
+
+        let env_ptr = engine
+            .write_to_memory(&env_bytes)
+            .map_err(wasm2_error_to_stderr(
+                "failed to write 'Env' to memory for WASM2 guest".to_string(),
+            ))?;
+        let msg_ptr = engine
+            .write_to_memory(&msg_bytes)
+            .map_err(wasm2_error_to_stderr(
+                "failed to write the message to memory for WASM2 guest".to_string(),
+            ))?;
+
+        let res_vec_ptr = match operation {
+            Wasm2Operation::Init => engine.init(env_ptr, msg_ptr),
+            Wasm2Operation::Query if engine.interface_version().query_has_env() => {
+                engine.query(Some(env_ptr), msg_ptr)
+            }
+            Wasm2Operation::Query => engine.query(None, msg_ptr),
+            Wasm2Operation::Handle => engine.handle(env_ptr, msg_ptr),
+            Wasm2Operation::Sudo | Wasm2Operation::Reply | Wasm2Operation::Verify => {
+                return Err(StdError::generic_err(format!(
+                    "WasmHarness does not support the {:?} operation",
+                    operation
+                )));
+            }
+        };
+        *storage_gas_used = engine.gas_used();
+        let res_vec_ptr = res_vec_ptr.map_err(wasm2_error_to_stderr(
+            "got an error while calling the WASM2 guest".to_string(),
+        ))?;
+
+        engine
+            .extract_vector(res_vec_ptr)
+            .map_err(wasm2_error_to_stderr(
+                "got an error extracting the results vector".to_string(),
+            ))
+    }
+}
+
+fn storage_diff(before: &MockStorage, after: &MockStorage) -> Vec<StorageChange> {
+    let before: BTreeMap<&[u8], &[u8]> = before.entries().collect();
+    let after: BTreeMap<&[u8], &[u8]> = after.entries().collect();
+    let keys: BTreeSet<&[u8]> = before.keys().chain(after.keys()).copied().collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let old = before.get(key);
+            let new = after.get(key);
+            if old == new {
+                return None;
+            }
+            Some(StorageChange {
+                key: key.to_vec(),
+                old: old.map(|value| value.to_vec()),
+                new: new.map(|value| value.to_vec()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{to_binary, ReadonlyStorage};
+    use libflate::gzip::Encoder;
+
+    use crate::fixtures::{contract, region, DB_WRITE};
+    use crate::gas::{GAS_WRITE_FLAT, GAS_WRITE_PER_BYTE};
+
+    /// `handle` sets "foo" and succeeds, `init` sets "foo" and fails, `query` answers "pong"
+    fn harness_contract() -> Vec<u8> {
+        let ok: HandleResult = Ok(HandleResponse::default());
+        let err: HandleResult = Err(StdError::generic_err("nope"));
+        let pong: QueryResult = to_binary("pong");
+        let (ok, err, pong) = (
+            to_vec(&ok).unwrap(),
+            to_vec(&err).unwrap(),
+            to_vec(&pong).unwrap(),
+        );
+        contract(
+            DB_WRITE,
+            &format!(
+                r#"
+                {}
+                {}
+                {}
+                {}
+                {}
+                (func (export "handle") (param i32 i32) (result i32)
+                    (call $db_write (i32.const 1024) (i32.const 1280))
+                    (i32.const 1536))
+                (func (export "init") (param i32 i32) (result i32)
+                    (call $db_write (i32.const 1024) (i32.const 1280))
+                    (i32.const 2048))
+                (func (export "query") (param i32 i32) (result i32)
+                    (i32.const 2560))
+                "#,
+                region(1024, b"foo"),
+                region(1280, b"bar"),
+                region(1536, &ok),
+                region(2048, &err),
+                region(2560, &pong),
+            ),
+        )
+    }
+
+    #[test]
+    fn handle_reports_gas_and_storage_diff() {
+        let mut harness = WasmHarness::new(&harness_contract()).unwrap();
+
+        let execution = harness.handle(mock_env("creator", &[]), &"do");
+        assert_eq!(execution.result.unwrap(), HandleResponse::default());
+        assert_eq!(execution.storage_gas_used, GAS_WRITE_FLAT + GAS_WRITE_PER_BYTE * 6);
+        assert_eq!(
+            execution.storage_diff,
+            vec![StorageChange {
+                key: b"foo".to_vec(),
+                old: None,
+                new: Some(b"bar".to_vec()),
+            }]
+        );
+
+        // writing the same value again changes nothing
+        let execution = harness.handle(mock_env("creator", &[]), &"do");
+        assert!(execution.result.is_ok());
+        assert_eq!(execution.storage_diff, vec![]);
+    }
+
+    #[test]
+    fn failed_calls_are_reverted() {
+        let mut harness = WasmHarness::new(&harness_contract()).unwrap();
+
+        let execution = harness.init(mock_env("creator", &[]), &"setup");
+        assert_eq!(execution.result, Err(StdError::generic_err("nope")));
+        assert_eq!(execution.storage_gas_used, GAS_WRITE_FLAT + GAS_WRITE_PER_BYTE * 6);
+        assert_eq!(execution.storage_diff, vec![]);
+        assert_eq!(harness.deps.storage.get(b"foo"), None);
+    }
+
+    #[test]
+    fn query_decodes_the_response() {
+        let mut harness = WasmHarness::new(&harness_contract()).unwrap();
+
+        let execution = harness.query::<_, String>(mock_env("creator", &[]), &"ping");
+        assert_eq!(execution.result.unwrap(), "pong");
+        assert_eq!(execution.storage_gas_used, 0);
+    }
+
+    #[test]
+    fn unsupported_operations_are_refused() {
+        let mut harness = WasmHarness::new(&harness_contract()).unwrap();
+
+        let execution = harness.call(
+            Wasm2Operation::Sudo,
+            &mock_env("creator", &[]),
+            &"do",
+            |res| Ok(res.to_vec()),
+        );
+        assert_eq!(
+            execution.result,
+            Err(StdError::generic_err(
+                "WasmHarness does not support the Sudo operation"
+            ))
+        );
+        assert_eq!(execution.storage_gas_used, 0);
+    }
+
+    #[test]
+    fn missing_init_is_reported() {
+        let wasm = contract("", "");
+        let mut harness = WasmHarness::new(&wasm).unwrap();
+
+        let execution = harness.init(mock_env("creator", &[]), &"setup");
+        assert_eq!(
+            execution.result,
+            Err(StdError::generic_err("Contract does not export 'init'"))
+        );
+    }
+
+    #[test]
+    fn loads_compressed_and_raw_contracts() {
+        let wasm = harness_contract();
+        let mut encoder = Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(&wasm).unwrap();
+        let compressed = encoder.finish().into_result().unwrap();
+
+        let mut harness = WasmHarness::new(&compressed).unwrap();
+        assert!(harness
+            .handle(mock_env("creator", &[]), &"do")
+            .result
+            .is_ok());
+
+        assert!(WasmHarness::new(&wasm).is_ok());
+        assert!(WasmHarness::new(b"not wasm").is_err());
+    }
+}
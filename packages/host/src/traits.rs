@@ -24,8 +24,12 @@ pub trait Wasm2Api {
         human_ptr_ptr: i32,
     ) -> Result<Option<RuntimeValue>, Trap>;
 
+    fn addr_validate_index(&mut self, human_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap>;
+
     fn query_chain_index(&mut self, query_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap>;
 
+    fn abort_index(&self, message_ptr_ptr: i32) -> Result<Option<RuntimeValue>, Trap>;
+
     #[cfg(feature = "debug-print")]
     fn debug_print_index(&self, message: i32) -> Result<Option<RuntimeValue>, Trap>;
 }
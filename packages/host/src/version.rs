@@ -0,0 +1,122 @@
+use std::fmt;
+
+use cosmwasm_std::StdError;
+use parity_wasm::elements::{Internal, Module};
+
+/// Contracts mark the interface level they are compiled for with an empty exported function
+/// named `wasm2_vm_version_<N>` (see exports.rs in wasm2-std).
+pub const INTERFACE_VERSION_EXPORT_PREFIX: &str = "wasm2_vm_version_";
+
+/// InterfaceVersion is the WASM2 VM interface level (exports, imports and their ABI) of a contract.
+/// Every version listed here is served side by side, so contracts compiled against an older
+/// wasm2-std keep working after the interface moves on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InterfaceVersion {
+    V1,
+    /// The query export receives the Env: `query(env_ptr, msg_ptr)` instead of `query(msg_ptr)`
+    V2,
+    /// Adds the `addr_validate` import
+    V3,
+    /// Adds the `abort` import, which reports the panic message of the contract
+    V4,
+}
+
+impl InterfaceVersion {
+    /// All interface versions this host is able to serve
+    pub const SUPPORTED: &'static [InterfaceVersion] = &[
+        InterfaceVersion::V1,
+        InterfaceVersion::V2,
+        InterfaceVersion::V3,
+        InterfaceVersion::V4,
+    ];
+
+    pub fn from_number(version: u32) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .find(|supported| supported.number() == version)
+            .copied()
+    }
+
+    pub fn number(&self) -> u32 {
+        match self {
+            InterfaceVersion::V1 => 1,
+            InterfaceVersion::V2 => 2,
+            InterfaceVersion::V3 => 3,
+            InterfaceVersion::V4 => 4,
+        }
+    }
+
+    /// Whether the query export of this interface takes a pointer to the Env
+    pub fn query_has_env(&self) -> bool {
+        !matches!(self, InterfaceVersion::V1)
+    }
+}
+
+impl fmt::Display for InterfaceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// detect_interface_version reads the `wasm2_vm_version_<N>` marker from the module exports.
+/// Modules without a marker, with several markers or with an unsupported version are refused,
+/// as they would otherwise fail later with an obscure instantiation or invocation error.
+pub fn detect_interface_version(module: &Module) -> Result<InterfaceVersion, StdError> {
+    let markers: Vec<&str> = module
+        .export_section()
+        .map(|section| {
+            section
+                .entries()
+                .iter()
+                .filter(|entry| matches!(entry.internal(), Internal::Function(_)))
+                .map(|entry| entry.field())
+                .filter(|field| field.starts_with(INTERFACE_VERSION_EXPORT_PREFIX))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let marker = match markers.as_slice() {
+        [marker] => *marker,
+        [] => {
+            return Err(StdError::GenericErr {
+                msg: format!(
+                    "WASM2 module does not export a '{}<N>' marker, was it built with wasm2-std?",
+                    INTERFACE_VERSION_EXPORT_PREFIX
+                ),
+                backtrace: None,
+            });
+        }
+        _ => {
+            return Err(StdError::GenericErr {
+                msg: format!(
+                    "WASM2 module exports more than one interface version marker: {}",
+                    markers.join(", ")
+                ),
+                backtrace: None,
+            });
+        }
+    };
+
+    let number = marker[INTERFACE_VERSION_EXPORT_PREFIX.len()..]
+        .parse::<u32>()
+        .map_err(|_| StdError::GenericErr {
+            msg: format!("WASM2 module exports a malformed interface version marker '{}'", marker),
+            backtrace: None,
+        })?;
+
+    InterfaceVersion::from_number(number).ok_or_else(|| {
+        let supported: Vec<String> = InterfaceVersion::SUPPORTED
+            .iter()
+            .map(|version| version.to_string())
+            .collect();
+        StdError::GenericErr {
+            msg: format!(
+                "WASM2 module requires VM interface version {}, this host supports: {}",
+                number,
+                supported.join(", ")
+            ),
+            backtrace: None,
+        }
+    })
+}
@@ -1,19 +1,69 @@
 use std::env::current_dir;
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, read_dir, remove_file, write};
+use std::path::Path;
 
-use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
-use cosmwasm_std::{CosmosMsg, Env, HandleResult, InitResult, MigrateResult, QueryResult};
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use teggle_wasm2_std::{CosmosMsg, Env, HandleResult, QueryResult};
 
+// Run with `cargo run --example schema --features schema`
 fn main() {
     let mut out_dir = current_dir().unwrap();
     out_dir.push("schema");
     create_dir_all(&out_dir).unwrap();
-    remove_schemas(&out_dir).unwrap();
-
-    export_schema(&schema_for!(Env), &out_dir);
-    export_schema(&schema_for!(CosmosMsg), &out_dir);
-    export_schema_with_title(&mut schema_for!(InitResult), &out_dir, "InitResult");
-    export_schema_with_title(&mut schema_for!(HandleResult), &out_dir, "HandleResult");
-    export_schema_with_title(&mut schema_for!(MigrateResult), &out_dir, "MigrateResult");
-    export_schema_with_title(&mut schema_for!(QueryResult), &out_dir, "QueryResult");
+    remove_schemas(&out_dir);
+
+    export_schema(schema_for!(Env), &out_dir);
+    export_schema(schema_for!(CosmosMsg), &out_dir);
+    export_schema_with_title(schema_for!(HandleResult), &out_dir, "HandleResult");
+    export_schema_with_title(schema_for!(QueryResult), &out_dir, "QueryResult");
+}
+
+/// Removes the schemas of a previous run, so renamed or removed types don't leave files behind
+fn remove_schemas(out_dir: &Path) {
+    for entry in read_dir(out_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            remove_file(path).unwrap();
+        }
+    }
+}
+
+fn export_schema(schema: RootSchema, out_dir: &Path) {
+    let title = schema
+        .schema
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.title.clone())
+        .unwrap();
+    write_schema(&schema, out_dir, &title);
+}
+
+/// Type aliases like HandleResult would otherwise be named after the type they alias
+fn export_schema_with_title(mut schema: RootSchema, out_dir: &Path, title: &str) {
+    schema.schema.metadata().title = Some(title.to_string());
+    write_schema(&schema, out_dir, title);
+}
+
+fn write_schema(schema: &RootSchema, out_dir: &Path, title: &str) {
+    let path = out_dir.join(format!("{}.json", to_snake_case(title)));
+    let json = serde_json::to_string_pretty(schema).unwrap();
+    write(&path, json + "\n").unwrap();
+    println!("Created {}", path.to_str().unwrap());
+}
+
+/// Converts a schema title like "CosmosMsg_for_Empty" to "cosmos_msg_for__empty"
+fn to_snake_case(title: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in title.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
 }
@@ -5,6 +5,7 @@ use crate::encoding::Binary;
 
 // Added Eq and Hash to allow this to be a key in a HashMap (MockQuerier)
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HumanAddr(pub String);
 
 impl HumanAddr {
@@ -51,7 +52,80 @@ impl From<String> for HumanAddr {
     }
 }
 
+/// Addr is a human readable address that was validated by `Api::addr_validate`.
+///
+/// Unlike `HumanAddr` it cannot be built from an arbitrary string in contract code, so holding one
+/// means the address has a valid format and is normalized.
+///
+/// WARNING: deserialization does NOT validate, as it has no access to the `Api`. It is meant for
+/// loading addresses the contract validated before storing them. Never use `Addr` in message
+/// types (`InitMsg`, `HandleMsg`, `QueryMsg`, ...): an `Addr` field accepts any string sent by
+/// the user. Take a `HumanAddr` there and pass it to `Api::addr_validate`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Addr(String);
+
+impl Addr {
+    /// Wraps an address without checking it.
+    ///
+    /// This is only meant for `Api` implementations overriding `addr_validate`, e.g. the one of
+    /// the host. Contracts must use `Api::addr_validate` instead.
+    pub fn unchecked<T: Into<String>>(addr: T) -> Self {
+        Addr(addr.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", &self.0)
+    }
+}
+
+impl AsRef<str> for Addr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for Addr {
+    fn eq(&self, rhs: &&str) -> bool {
+        self.0 == *rhs
+    }
+}
+
+impl From<Addr> for String {
+    fn from(addr: Addr) -> Self {
+        addr.0
+    }
+}
+
+// Messages and queries still take a HumanAddr
+impl From<Addr> for HumanAddr {
+    fn from(addr: Addr) -> Self {
+        HumanAddr(addr.0)
+    }
+}
+
+impl From<&Addr> for HumanAddr {
+    fn from(addr: &Addr) -> Self {
+        HumanAddr(addr.0.clone())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CanonicalAddr(pub Binary);
 
 impl From<&[u8]> for CanonicalAddr {
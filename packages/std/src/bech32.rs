@@ -0,0 +1,326 @@
+//! Bech32 address encoding (BIP-0173), as used for the human readable addresses of Cosmos chains
+
+use crate::addresses::{CanonicalAddr, HumanAddr};
+use crate::encoding::Binary;
+use crate::errors::{StdError, StdResult};
+use crate::traits::Api;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: char = '1';
+const CHECKSUM_LENGTH: usize = 6;
+/// The maximum length of a bech32 string as defined by BIP-0173
+const MAX_LENGTH: usize = 90;
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+/// Bech32Api converts between bech32 human addresses with the given human readable part
+/// (e.g. "secret") and the raw bytes they encode.
+///
+/// It can be used as the `Api` of the host, and is the address codec of `MockApi::new_bech32`,
+/// so contract tests and the runtime convert addresses the same way.
+///
+/// The prefix is a `&'static str` because every `Api` must be `Copy`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bech32Api {
+    prefix: &'static str,
+}
+
+impl Bech32Api {
+    pub fn new(prefix: &'static str) -> Self {
+        Bech32Api { prefix }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+}
+
+impl Api for Bech32Api {
+    fn canonical_address(&self, human: &HumanAddr) -> StdResult<CanonicalAddr> {
+        let (hrp, data) = bech32_decode(human.as_str())?;
+        if hrp != self.prefix {
+            return Err(StdError::generic_err(format!(
+                "Invalid input: expected prefix '{}', got '{}'",
+                self.prefix, hrp
+            )));
+        }
+        Ok(CanonicalAddr(Binary(data)))
+    }
+
+    fn human_address(&self, canonical: &CanonicalAddr) -> StdResult<HumanAddr> {
+        if canonical.is_empty() {
+            return Err(StdError::generic_err(
+                "Invalid input: canonical address empty",
+            ));
+        }
+        bech32_encode(self.prefix, canonical.as_slice()).map(HumanAddr)
+    }
+}
+
+/// bech32_encode encodes the data with the given human readable part.
+/// The result is lowercase, which is the normalized form of a bech32 string.
+pub fn bech32_encode(hrp: &str, data: &[u8]) -> StdResult<String> {
+    validate_hrp(hrp)?;
+    if hrp.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(StdError::generic_err(
+            "Invalid input: human readable part must be lowercase",
+        ));
+    }
+
+    let values = convert_bits(data, 8, 5, true)?;
+    let length = hrp.len() + 1 + values.len() + CHECKSUM_LENGTH;
+    if length > MAX_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Invalid input: bech32 string would be {} characters long, the maximum is {}",
+            length, MAX_LENGTH
+        )));
+    }
+
+    let checksum = create_checksum(hrp, &values);
+    let mut encoded = String::with_capacity(length);
+    encoded.push_str(hrp);
+    encoded.push(SEPARATOR);
+    encoded.extend(
+        values
+            .iter()
+            .chain(checksum.iter())
+            .map(|&value| CHARSET[value as usize] as char),
+    );
+    Ok(encoded)
+}
+
+/// bech32_decode verifies the checksum of a bech32 string and returns its human readable part
+/// and data. Uppercase strings are accepted, mixed case ones are not. The human readable part
+/// is returned lowercase.
+pub fn bech32_decode(encoded: &str) -> StdResult<(String, Vec<u8>)> {
+    if encoded.len() > MAX_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "Invalid input: bech32 string longer than {} characters",
+            MAX_LENGTH
+        )));
+    }
+    let has_lower = encoded.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = encoded.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(StdError::generic_err(
+            "Invalid input: bech32 string has mixed case",
+        ));
+    }
+    let encoded = encoded.to_ascii_lowercase();
+
+    let separator = encoded
+        .rfind(SEPARATOR)
+        .ok_or_else(|| StdError::generic_err("Invalid input: bech32 string has no separator"))?;
+    let (hrp, data) = (&encoded[..separator], &encoded[separator + 1..]);
+    validate_hrp(hrp)?;
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(StdError::generic_err(
+            "Invalid input: bech32 string too short",
+        ));
+    }
+
+    let values = data
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&charset_c| charset_c == c)
+                .map(|position| position as u8)
+                .ok_or_else(|| {
+                    StdError::generic_err(format!(
+                        "Invalid input: invalid bech32 character '{}'",
+                        c as char
+                    ))
+                })
+        })
+        .collect::<StdResult<Vec<u8>>>()?;
+
+    let mut checked = expand_hrp(hrp);
+    checked.extend_from_slice(&values);
+    if polymod(&checked) != 1 {
+        return Err(StdError::generic_err(
+            "Invalid input: invalid bech32 checksum",
+        ));
+    }
+
+    let data = convert_bits(&values[..values.len() - CHECKSUM_LENGTH], 5, 8, false)?;
+    Ok((hrp.to_string(), data))
+}
+
+fn validate_hrp(hrp: &str) -> StdResult<()> {
+    if hrp.is_empty() {
+        return Err(StdError::generic_err(
+            "Invalid input: human readable part empty",
+        ));
+    }
+    if hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(StdError::generic_err(
+            "Invalid input: invalid character in human readable part",
+        ));
+    }
+    Ok(())
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut checksum: u32 = 1;
+    for &value in values {
+        let top = checksum >> 25;
+        checksum = (checksum & 0x1ff_ffff) << 5 ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// The human readable part is part of the checksum, split in its high and low bits
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|c| c & 0x1f));
+    expanded
+}
+
+fn create_checksum(hrp: &str, values: &[u8]) -> Vec<u8> {
+    let mut input = expand_hrp(hrp);
+    input.extend_from_slice(values);
+    input.extend_from_slice(&[0; CHECKSUM_LENGTH]);
+    let checksum = polymod(&input) ^ 1;
+    (0..CHECKSUM_LENGTH)
+        .map(|i| ((checksum >> (5 * (5 - i))) & 0x1f) as u8)
+        .collect()
+}
+
+/// Regroups the bits of `data` from groups of `from` bits into groups of `to` bits.
+/// Encoding pads the last group with zeros, decoding refuses any non-zero padding.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> StdResult<Vec<u8>> {
+    let mut accumulator: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    // only the bits not yet converted are kept, so the accumulator cannot overflow
+    let max_accumulator: u32 = (1 << (from + to - 1)) - 1;
+    let mut converted = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for &value in data {
+        accumulator = ((accumulator << from) | u32::from(value)) & max_accumulator;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((accumulator >> bits) & max_value) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            converted.push(((accumulator << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((accumulator << (to - bits)) & max_value) != 0 {
+        return Err(StdError::generic_err(
+            "Invalid input: invalid padding in bech32 data",
+        ));
+    }
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bech32_decode_works_for_bip173_vectors() {
+        for valid in &[
+            "A12UEL5L",
+            "a12uel5l",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ] {
+            bech32_decode(valid).unwrap();
+        }
+
+        for invalid in &[
+            // hrp character out of range
+            "\u{20}1nwldj5",
+            // overall max length exceeded
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+            // no separator
+            "pzry9x0s0muk",
+            // empty hrp
+            "1pzry9x0s0muk",
+            // invalid data character
+            "x1b4n0q5v",
+            // too short checksum
+            "li1dgmt3",
+            // checksum calculated with uppercase form of hrp
+            "A1G7SGD8",
+            // mixed case
+            "a12UEL5L",
+        ] {
+            assert!(bech32_decode(invalid).is_err(), "{} must not decode", invalid);
+        }
+    }
+
+    #[test]
+    fn bech32_encode_decode_round_trip() {
+        let data: Vec<u8> = (0u8..20).collect();
+        let encoded = bech32_encode("secret", &data).unwrap();
+        assert!(encoded.starts_with("secret1"));
+        assert_eq!(
+            bech32_decode(&encoded).unwrap(),
+            ("secret".to_string(), data)
+        );
+
+        // uppercase input decodes to the same data
+        let (hrp, _) = bech32_decode(&encoded.to_uppercase()).unwrap();
+        assert_eq!(hrp, "secret");
+    }
+
+    #[test]
+    fn bech32_encode_works() {
+        // the data values of this BIP-0173 vector are 0 to 31, i.e. exactly 20 bytes
+        let vector = "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw";
+        let (hrp, data) = bech32_decode(vector).unwrap();
+        assert_eq!(data.len(), 20);
+        assert_eq!(bech32_encode(&hrp, &data).unwrap(), vector);
+
+        assert!(bech32_encode("", &data).is_err());
+        assert!(bech32_encode("Secret", &data).is_err());
+        assert!(bech32_encode("secret", &[0u8; 60]).is_err());
+    }
+
+    #[test]
+    fn bech32_api_round_trips_any_bytes() {
+        let api = Bech32Api::new("secret");
+        // zero bytes are kept, unlike the default MockApi
+        let canonical = CanonicalAddr(Binary(vec![
+            0, 1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ]));
+        let human = api.human_address(&canonical).unwrap();
+        assert_eq!(api.canonical_address(&human).unwrap(), canonical);
+        assert_eq!(
+            api.addr_validate(human.as_str()).unwrap().as_str(),
+            human.as_str()
+        );
+
+        // wrong prefix
+        let other = Bech32Api::new("cosmos").human_address(&canonical).unwrap();
+        let err = api.canonical_address(&other).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Invalid input: expected prefix 'secret', got 'cosmos'")
+        );
+        // uppercase is valid bech32, but not normalized
+        let err = api
+            .addr_validate(&human.as_str().to_uppercase())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Invalid input: address not normalized")
+        );
+    }
+}
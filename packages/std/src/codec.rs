@@ -0,0 +1,115 @@
+//! Codecs turn values into bytes and back, e.g. for storage.
+//!
+//! Messages and responses always use JSON, the codec is only a choice for data the contract
+//! reads back itself.
+#[cfg(feature = "bincode")]
+use bincode::Options;
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "bincode")]
+use std::any::type_name;
+
+#[cfg(feature = "bincode")]
+use crate::errors::StdError;
+use crate::errors::StdResult;
+use crate::serde::{from_slice, to_vec};
+
+pub trait Codec {
+    fn encode<T: Serialize + ?Sized>(data: &T) -> StdResult<Vec<u8>>;
+    fn decode<T: DeserializeOwned>(value: &[u8]) -> StdResult<T>;
+}
+
+/// Json is the encoding of messages, and the default codec of the storage helpers
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<T: Serialize + ?Sized>(data: &T) -> StdResult<Vec<u8>> {
+        to_vec(data)
+    }
+
+    fn decode<T: DeserializeOwned>(value: &[u8]) -> StdResult<T> {
+        from_slice(value)
+    }
+}
+
+/// Bincode is a compact binary encoding, with variable length integers. It is much faster than
+/// JSON in the interpreter and produces smaller values. It needs the `bincode` feature.
+///
+/// It is not self-describing: field names are not stored, so changing a stored type (even adding
+/// a field with `#[serde(default)]`) breaks loading existing values, and types relying on
+/// `deserialize_any` (e.g. `#[serde(untagged)]` enums) cannot be decoded.
+#[cfg(feature = "bincode")]
+pub struct Bincode;
+
+#[cfg(feature = "bincode")]
+impl Codec for Bincode {
+    fn encode<T: Serialize + ?Sized>(data: &T) -> StdResult<Vec<u8>> {
+        bincode_options()
+            .serialize(data)
+            .map_err(|e| StdError::serialize_err(type_name::<T>(), e))
+    }
+
+    fn decode<T: DeserializeOwned>(value: &[u8]) -> StdResult<T> {
+        bincode_options()
+            .deserialize(value)
+            .map_err(|e| StdError::parse_err(type_name::<T>(), e))
+    }
+}
+
+/// Variable length little endian integers, trailing bytes are refused
+#[cfg(feature = "bincode")]
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "bincode")]
+    use crate::coins::coins;
+    use crate::coins::Coin;
+    #[cfg(feature = "bincode")]
+    use crate::math::Uint128;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Offer {
+        Open { price: Vec<Coin>, ends_at: u64 },
+        Closed {},
+    }
+
+    #[test]
+    fn json_codec_matches_to_vec() {
+        let offer = Offer::Closed {};
+        assert_eq!(Json::encode(&offer).unwrap(), br#"{"closed":{}}"#.to_vec());
+        assert_eq!(Json::decode::<Offer>(br#"{"closed":{}}"#).unwrap(), offer);
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_codec_round_trips() {
+        let offer = Offer::Open {
+            price: coins(1234, "uscrt"),
+            ends_at: 7,
+        };
+        let encoded = Bincode::encode(&offer).unwrap();
+        assert!(encoded.len() < Json::encode(&offer).unwrap().len());
+        assert_eq!(Bincode::decode::<Offer>(&encoded).unwrap(), offer);
+
+        // string encoded types keep their serde implementation
+        let encoded = Bincode::encode(&Uint128(5)).unwrap();
+        assert_eq!(Bincode::decode::<Uint128>(&encoded).unwrap(), Uint128(5));
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_codec_reports_errors() {
+        let mut encoded = Bincode::encode(&Offer::Closed {}).unwrap();
+        encoded.push(0);
+        match Bincode::decode::<Offer>(&encoded).unwrap_err() {
+            StdError::ParseErr { target, .. } => assert!(target.ends_with("Offer")),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(Bincode::decode::<Offer>(&[9]).is_err());
+    }
+}
@@ -1,8 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
 
+use crate::errors::{StdError, StdResult};
 use crate::math::Uint128;
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Coin {
     pub denom: String,
     pub amount: Uint128,
@@ -36,9 +41,166 @@ pub fn has_coins(coins: &[Coin], required: &Coin) -> bool {
         .unwrap_or(false)
 }
 
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// Coins is a set of coins that is always normalized: sorted by denom, with unique denoms
+/// and without zero amounts. It serializes like a `Vec<Coin>`, so it can replace one in messages.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+#[serde(try_from = "Vec<Coin>", into = "Vec<Coin>")]
+pub struct Coins(BTreeMap<String, Uint128>);
+
+impl Coins {
+    pub fn new() -> Self {
+        Coins::default()
+    }
+
+    /// Returns the amount of the given denom, which is zero if the denom is absent
+    pub fn amount_of(&self, denom: &str) -> Uint128 {
+        self.0.get(denom).copied().unwrap_or_default()
+    }
+
+    /// Returns the denoms in sorted order
+    pub fn denoms(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the coins sorted by denom
+    pub fn to_vec(&self) -> Vec<Coin> {
+        self.0
+            .iter()
+            .map(|(denom, amount)| Coin {
+                denom: denom.clone(),
+                amount: *amount,
+            })
+            .collect()
+    }
+
+    /// Adds a coin. On overflow an error is returned and the set is left unchanged.
+    pub fn add(&mut self, coin: Coin) -> StdResult<()> {
+        let amount = self.amount_of(&coin.denom).checked_add(coin.amount)?;
+        self.set(coin.denom, amount);
+        Ok(())
+    }
+
+    /// Subtracts a coin. If the set holds less than the amount an error is returned
+    /// and the set is left unchanged.
+    pub fn sub(&mut self, coin: Coin) -> StdResult<()> {
+        let amount = self.amount_of(&coin.denom).checked_sub(coin.amount)?;
+        self.set(coin.denom, amount);
+        Ok(())
+    }
+
+    /// Returns the sum of both sets
+    pub fn checked_add(&self, other: &Coins) -> StdResult<Coins> {
+        let mut sum = self.clone();
+        for coin in other.to_vec() {
+            sum.add(coin)?;
+        }
+        Ok(sum)
+    }
+
+    /// Returns the difference of both sets, fails if other holds more of any denom than self
+    pub fn checked_sub(&self, other: &Coins) -> StdResult<Coins> {
+        let mut difference = self.clone();
+        for coin in other.to_vec() {
+            difference.sub(coin)?;
+        }
+        Ok(difference)
+    }
+
+    /// Returns true if self holds at least the required amount of the coin's denom
+    pub fn has(&self, required: &Coin) -> bool {
+        self.amount_of(&required.denom) >= required.amount
+    }
+
+    /// Returns true if other holds at least the amount of every coin in self
+    pub fn is_subset(&self, other: &Coins) -> bool {
+        self.0
+            .iter()
+            .all(|(denom, amount)| other.amount_of(denom) >= *amount)
+    }
+
+    /// Returns true if self holds at least the amount of every required coin
+    pub fn has_all(&self, required: &Coins) -> bool {
+        required.is_subset(self)
+    }
+
+    /// Keeps the set normalized by dropping zero amounts
+    fn set(&mut self, denom: String, amount: Uint128) {
+        if amount.is_zero() {
+            self.0.remove(&denom);
+        } else {
+            self.0.insert(denom, amount);
+        }
+    }
+}
+
+impl TryFrom<&[Coin]> for Coins {
+    type Error = StdError;
+
+    /// Sorts the coins and drops zero amounts. Duplicate denoms are refused,
+    /// as they are most likely a mistake of the sender.
+    fn try_from(coins: &[Coin]) -> StdResult<Self> {
+        let mut set = Coins::new();
+        for coin in coins {
+            if set.0.contains_key(&coin.denom) {
+                return Err(StdError::generic_err(format!(
+                    "Duplicate denom {} in coins",
+                    coin.denom
+                )));
+            }
+            set.set(coin.denom.clone(), coin.amount);
+        }
+        Ok(set)
+    }
+}
+
+impl TryFrom<Vec<Coin>> for Coins {
+    type Error = StdError;
+
+    fn try_from(coins: Vec<Coin>) -> StdResult<Self> {
+        Coins::try_from(coins.as_slice())
+    }
+}
+
+impl From<Coin> for Coins {
+    fn from(coin: Coin) -> Self {
+        let mut set = Coins::new();
+        set.set(coin.denom, coin.amount);
+        set
+    }
+}
+
+impl From<Coins> for Vec<Coin> {
+    fn from(coins: Coins) -> Self {
+        coins.to_vec()
+    }
+}
+
+impl fmt::Display for Coins {
+    /// Formats the coins like the Cosmos SDK, e.g. "555BTC,12345ETH"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coins: Vec<String> = self.to_vec().iter().map(Coin::to_string).collect();
+        f.write_str(&coins.join(","))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::serde::{from_slice, to_vec};
 
     #[test]
     fn has_coins_matches() {
@@ -47,4 +209,70 @@ mod test {
         // less than same type
         assert!(has_coins(&wallet, &coin(777, "ETH")));
     }
+
+    #[test]
+    fn coins_are_normalized() {
+        let wallet =
+            Coins::try_from(vec![coin(12345, "ETH"), coin(0, "ATOM"), coin(555, "BTC")]).unwrap();
+        assert_eq!(wallet.to_vec(), vec![coin(555, "BTC"), coin(12345, "ETH")]);
+        assert_eq!(wallet.denoms(), vec!["BTC", "ETH"]);
+        assert_eq!(wallet.amount_of("ETH"), Uint128(12345));
+        assert_eq!(wallet.amount_of("ATOM"), Uint128::zero());
+        assert_eq!(wallet.to_string(), "555BTC,12345ETH");
+
+        let err = Coins::try_from(vec![coin(1, "ETH"), coin(2, "ETH")]).unwrap_err();
+        assert_eq!(err, StdError::generic_err("Duplicate denom ETH in coins"));
+    }
+
+    #[test]
+    fn coins_add_and_sub() {
+        let mut wallet = Coins::from(coin(100, "ETH"));
+        wallet.add(coin(50, "ETH")).unwrap();
+        wallet.add(coin(7, "BTC")).unwrap();
+        assert_eq!(wallet.to_vec(), vec![coin(7, "BTC"), coin(150, "ETH")]);
+
+        wallet.sub(coin(7, "BTC")).unwrap();
+        assert_eq!(wallet.to_vec(), vec![coin(150, "ETH")]);
+
+        // failed operations leave the set unchanged
+        assert!(wallet.sub(coin(151, "ETH")).is_err());
+        assert!(wallet.sub(coin(1, "BTC")).is_err());
+        assert!(wallet.add(coin(u128::MAX, "ETH")).is_err());
+        assert_eq!(wallet.to_vec(), vec![coin(150, "ETH")]);
+
+        let other = Coins::try_from(vec![coin(50, "ETH"), coin(3, "BTC")]).unwrap();
+        let sum = wallet.checked_add(&other).unwrap();
+        assert_eq!(sum.to_vec(), vec![coin(3, "BTC"), coin(200, "ETH")]);
+        assert_eq!(sum.checked_sub(&other).unwrap(), wallet);
+        assert!(wallet.checked_sub(&other).is_err());
+    }
+
+    #[test]
+    fn coins_subsets() {
+        let wallet = Coins::try_from(vec![coin(12345, "ETH"), coin(555, "BTC")]).unwrap();
+        let required = Coins::try_from(vec![coin(777, "ETH"), coin(555, "BTC")]).unwrap();
+        assert!(required.is_subset(&wallet));
+        assert!(wallet.has_all(&required));
+        assert!(wallet.has(&coin(555, "BTC")));
+        assert!(!wallet.has(&coin(556, "BTC")));
+
+        let too_much = Coins::try_from(vec![coin(1, "ATOM")]).unwrap();
+        assert!(!wallet.has_all(&too_much));
+        assert!(Coins::new().is_subset(&wallet));
+    }
+
+    #[test]
+    fn coins_serialize_like_vec() {
+        let wallet = Coins::try_from(vec![coin(12345, "ETH"), coin(555, "BTC")]).unwrap();
+        let serialized = to_vec(&wallet).unwrap();
+        assert_eq!(
+            serialized,
+            to_vec(&vec![coin(555, "BTC"), coin(12345, "ETH")]).unwrap()
+        );
+        let parsed: Coins = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, wallet);
+
+        let duplicate = br#"[{"denom":"ETH","amount":"1"},{"denom":"ETH","amount":"2"}]"#;
+        assert!(from_slice::<Coins>(duplicate).is_err());
+    }
 }
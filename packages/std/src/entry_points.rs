@@ -31,6 +31,7 @@
 /// # type QueryMsg = ();
 /// pub fn query<S: Storage, A: Api, Q: Querier>(
 ///     deps: &Extern<S, A, Q>,
+///     env: Env,
 ///     msg: QueryMsg,
 /// ) -> QueryResult {
 /// #   Ok(Binary(Vec::new()))
@@ -38,12 +39,74 @@
 /// ```
 /// Where `InitMsg`, `HandleMsg`, and `QueryMsg` are types that implement `DeserializeOwned + JsonSchema`
 ///
+/// Instead of `StdError`, the functions can fail with a contract defined error type that implements
+/// `Into<StdError>`. Mapping it to `StdError::ContractErr` lets typed error codes reach clients:
+/// ```
+/// # use wasm2_std::{
+/// #     Storage, Api, Querier, Extern, Env, HandleResponse, StdError,
+/// # };
+/// # type HandleMsg = ();
+/// pub enum ContractError {
+///     Expired,
+///     Std(StdError),
+/// }
+///
+/// impl From<ContractError> for StdError {
+///     fn from(err: ContractError) -> Self {
+///         match err {
+///             ContractError::Expired => StdError::contract_err(1, "Offer expired"),
+///             ContractError::Std(err) => err,
+///         }
+///     }
+/// }
+///
+/// pub fn handle<S: Storage, A: Api, Q: Querier>(
+///     deps: &mut Extern<S, A, Q>,
+///     env: Env,
+///     msg: HandleMsg,
+/// ) -> Result<HandleResponse, ContractError> {
+/// #   Ok(Default::default())
+/// }
+/// ```
+///
+/// Contracts called by the runtime itself can also export a `sudo` function, by passing `sudo`
+/// as an extra argument to the macro:
+/// ```
+/// # use wasm2_std::{
+/// #     Storage, Api, Querier, Extern, Env, HandleResult, SudoMsg,
+/// # };
+/// pub fn sudo<S: Storage, A: Api, Q: Querier>(
+///     deps: &mut Extern<S, A, Q>,
+///     env: Env,
+///     msg: SudoMsg,
+/// ) -> HandleResult {
+/// #   Ok(Default::default())
+/// }
+/// ```
+///
+/// Contracts that send submessages receive their results in a `reply` function, exported by
+/// passing `reply` to the macro:
+/// ```
+/// # use wasm2_std::{
+/// #     Storage, Api, Querier, Extern, Env, HandleResult, Reply,
+/// # };
+/// pub fn reply<S: Storage, A: Api, Q: Querier>(
+///     deps: &mut Extern<S, A, Q>,
+///     env: Env,
+///     msg: Reply,
+/// ) -> HandleResult {
+/// #   Ok(Default::default())
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```ignore
 /// use contract; // The contract module
 ///
 /// wasm2_std::create_entry_points!(contract);
+/// // or, with the optional exports
+/// wasm2_std::create_entry_points!(contract, sudo, reply);
 /// ```
 #[macro_export]
 macro_rules! create_entry_points {
@@ -60,12 +123,53 @@ macro_rules! create_entry_points {
 
     (@migration; $contract:ident, false) => {};
 
-    (@inner; $contract:ident, migration = $migration:tt) => {
+    (@sudo; $contract:ident, true) => {
+        #[no_mangle]
+        extern "C" fn sudo(env_ptr: u32, msg_ptr: u32) -> u32 {
+            do_sudo(
+                &$contract::sudo::<ExternalStorage, ExternalApi, ExternalQuerier>,
+                env_ptr,
+                msg_ptr,
+            )
+        }
+    };
+
+    (@sudo; $contract:ident, false) => {};
+
+    (@reply; $contract:ident, true) => {
+        #[no_mangle]
+        extern "C" fn reply(env_ptr: u32, msg_ptr: u32) -> u32 {
+            do_reply(
+                &$contract::reply::<ExternalStorage, ExternalApi, ExternalQuerier>,
+                env_ptr,
+                msg_ptr,
+            )
+        }
+    };
+
+    (@reply; $contract:ident, false) => {};
+
+    // @options collects the optional exports passed after the contract module, in any order
+    (@options; $contract:ident, [$migration:tt, $sudo:tt, $reply:tt], sudo $(, $rest:ident)*) => {
+        $crate::create_entry_points!(@options; $contract, [$migration, true, $reply] $(, $rest)*);
+    };
+
+    (@options; $contract:ident, [$migration:tt, $sudo:tt, $reply:tt], reply $(, $rest:ident)*) => {
+        $crate::create_entry_points!(@options; $contract, [$migration, $sudo, true] $(, $rest)*);
+    };
+
+    (@options; $contract:ident, [$migration:tt, $sudo:tt, $reply:tt]) => {
+        $crate::create_entry_points!(
+            @inner; $contract, migration = $migration, sudo = $sudo, reply = $reply
+        );
+    };
+
+    (@inner; $contract:ident, migration = $migration:tt, sudo = $sudo:tt, reply = $reply:tt) => {
         mod wasm {
             use super::$contract;
             use wasm2_std::{
-                do_handle, do_init, do_migrate, do_query, ExternalApi, ExternalQuerier,
-                ExternalStorage,
+                do_handle, do_init, do_migrate, do_query, do_reply, do_sudo, ExternalApi,
+                ExternalQuerier, ExternalStorage,
             };
 
             #[no_mangle]
@@ -87,22 +191,25 @@ macro_rules! create_entry_points {
             }
 
             #[no_mangle]
-            extern "C" fn query(msg_ptr: u32) -> u32 {
+            extern "C" fn query(env_ptr: u32, msg_ptr: u32) -> u32 {
                 do_query(
                     &$contract::query::<ExternalStorage, ExternalApi, ExternalQuerier>,
+                    env_ptr,
                     msg_ptr,
                 )
             }
 
             $crate::create_entry_points!(@migration; $contract, $migration);
+            $crate::create_entry_points!(@sudo; $contract, $sudo);
+            $crate::create_entry_points!(@reply; $contract, $reply);
 
-            // Other C externs like cosmwasm_vm_version_3, allocate, deallocate are available
+            // Other C externs like wasm2_vm_version_4, allocate, deallocate are available
             // automatically because we `use wasm2_std`.
         }
     };
 
-    ($contract:ident) => {
-        $crate::create_entry_points!(@inner; $contract, migration = false);
+    ($contract:ident $(, $export:ident)*) => {
+        $crate::create_entry_points!(@options; $contract, [false, false, false] $(, $export)*);
     };
 }
 
@@ -131,7 +238,7 @@ macro_rules! create_entry_points {
 /// ```
 #[macro_export]
 macro_rules! create_entry_points_with_migration {
-    ($contract:ident) => {
-        $crate::create_entry_points!(@inner; $contract, migration = true);
+    ($contract:ident $(, $export:ident)*) => {
+        $crate::create_entry_points!(@options; $contract, [true, false, false] $(, $export)*);
     };
 }
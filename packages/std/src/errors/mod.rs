@@ -1,5 +1,5 @@
 mod std_error;
 mod system_error;
 
-pub use std_error::{StdError, StdResult};
+pub use std_error::{OverflowOperation, PaymentError, StdError, StdResult};
 pub use system_error::{SystemError, SystemResult};
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
+use std::fmt;
+
+use crate::coins::Coin;
 
 /// Structured error type for init, handle and query.
 ///
@@ -20,6 +23,7 @@ use snafu::Snafu;
 /// - Add creator function in std_error_helpers.rs
 /// - Regenerate schemas
 #[derive(Debug, Serialize, Deserialize, Snafu)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum StdError {
@@ -30,6 +34,14 @@ pub enum StdError {
         #[serde(skip)]
         backtrace: Option<snafu::Backtrace>,
     },
+    /// An error defined by the contract. The code is for clients to match on, the message for humans.
+    #[snafu(display("Contract error {}: {}", code, msg))]
+    ContractErr {
+        code: u32,
+        msg: String,
+        #[serde(skip)]
+        backtrace: Option<snafu::Backtrace>,
+    },
     #[snafu(display("Invalid Base64 string: {}", msg))]
     InvalidBase64 {
         msg: String,
@@ -78,6 +90,89 @@ pub enum StdError {
         #[serde(skip)]
         backtrace: Option<snafu::Backtrace>,
     },
+    /// The result of a checked math operation does not fit into its type
+    #[snafu(display("Cannot {} with {} and {}", operation, operand1, operand2))]
+    Overflow {
+        operation: OverflowOperation,
+        operand1: String,
+        operand2: String,
+        #[serde(skip)]
+        backtrace: Option<snafu::Backtrace>,
+    },
+    #[snafu(display("Cannot divide {} by zero", operand))]
+    DivideByZero {
+        operand: String,
+        #[serde(skip)]
+        backtrace: Option<snafu::Backtrace>,
+    },
+    /// The funds sent with a message do not match what the contract expects
+    #[snafu(display("Invalid payment: {}", reason))]
+    InvalidPayment {
+        reason: PaymentError,
+        #[serde(skip)]
+        backtrace: Option<snafu::Backtrace>,
+    },
+}
+
+/// The math operation that caused an `StdError::Overflow`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowOperation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+/// The reason of an `StdError::InvalidPayment`, see the helpers in payment.rs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentError {
+    /// Funds were required but none were sent
+    NoFunds {},
+    /// The message does not accept funds but some were sent
+    NonPayable {},
+    /// Only one denom is accepted but several were sent
+    MultipleDenoms {},
+    /// The required denom was not sent
+    MissingDenom { denom: String },
+    /// A denom other than the accepted one was sent
+    ExtraDenom { denom: String },
+    /// The required denom was sent, but not enough of it
+    InsufficientFunds { required: Coin, sent: Coin },
+}
+
+impl fmt::Display for PaymentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaymentError::NoFunds {} => write!(f, "No funds sent"),
+            PaymentError::NonPayable {} => write!(f, "This message does not accept funds"),
+            PaymentError::MultipleDenoms {} => write!(f, "Sent more than one denomination"),
+            PaymentError::MissingDenom { denom } => write!(f, "Must send '{}' to pay", denom),
+            PaymentError::ExtraDenom { denom } => {
+                write!(f, "Received unsupported denom '{}'", denom)
+            }
+            PaymentError::InsufficientFunds { required, sent } => {
+                write!(f, "Sent {} but at least {} is required", sent, required)
+            }
+        }
+    }
+}
+
+impl fmt::Display for OverflowOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OverflowOperation::Add => "add",
+            OverflowOperation::Sub => "sub",
+            OverflowOperation::Mul => "mul",
+            OverflowOperation::Div => "div",
+            OverflowOperation::Pow => "pow",
+        };
+        f.write_str(name)
+    }
 }
 
 impl StdError {
@@ -85,6 +180,14 @@ impl StdError {
         GenericErr { msg: msg.into() }.build()
     }
 
+    pub fn contract_err<S: ToString>(code: u32, msg: S) -> Self {
+        ContractErr {
+            code,
+            msg: msg.to_string(),
+        }
+        .build()
+    }
+
     pub fn invalid_base64<S: ToString>(msg: S) -> Self {
         InvalidBase64 {
             msg: msg.to_string(),
@@ -130,6 +233,26 @@ impl StdError {
     pub fn unauthorized() -> Self {
         Unauthorized {}.build()
     }
+
+    pub fn overflow<U: ToString>(operation: OverflowOperation, operand1: U, operand2: U) -> Self {
+        Overflow {
+            operation,
+            operand1: operand1.to_string(),
+            operand2: operand2.to_string(),
+        }
+        .build()
+    }
+
+    pub fn invalid_payment(reason: PaymentError) -> Self {
+        InvalidPayment { reason }.build()
+    }
+
+    pub fn divide_by_zero<U: ToString>(operand: U) -> Self {
+        DivideByZero {
+            operand: operand.to_string(),
+        }
+        .build()
+    }
 }
 
 impl PartialEq for StdError {
@@ -150,6 +273,18 @@ impl PartialEq for StdError {
                     backtrace: _,
                 },
             ) => msg == msg2,
+            (
+                StdError::ContractErr {
+                    code,
+                    msg,
+                    backtrace: _,
+                },
+                StdError::ContractErr {
+                    code: code2,
+                    msg: msg2,
+                    backtrace: _,
+                },
+            ) => code == code2 && msg == msg2,
             (
                 StdError::InvalidBase64 { msg, backtrace: _ },
                 StdError::InvalidBase64 {
@@ -210,6 +345,40 @@ impl PartialEq for StdError {
                     backtrace: _,
                 },
             ) => minuend == minued2 && subtrahend == subtrahend2,
+            (
+                StdError::Overflow {
+                    operation,
+                    operand1,
+                    operand2,
+                    backtrace: _,
+                },
+                StdError::Overflow {
+                    operation: operation2,
+                    operand1: operand1_2,
+                    operand2: operand2_2,
+                    backtrace: _,
+                },
+            ) => operation == operation2 && operand1 == operand1_2 && operand2 == operand2_2,
+            (
+                StdError::DivideByZero {
+                    operand,
+                    backtrace: _,
+                },
+                StdError::DivideByZero {
+                    operand: operand2,
+                    backtrace: _,
+                },
+            ) => operand == operand2,
+            (
+                StdError::InvalidPayment {
+                    reason,
+                    backtrace: _,
+                },
+                StdError::InvalidPayment {
+                    reason: reason2,
+                    backtrace: _,
+                },
+            ) => reason == reason2,
             _ => false,
         }
     }
@@ -252,6 +421,19 @@ mod test {
         }
     }
 
+    #[test]
+    fn contract_err_works() {
+        let error = StdError::contract_err(42, "Offer expired");
+        match &error {
+            StdError::ContractErr { code, msg, .. } => {
+                assert_eq!(*code, 42);
+                assert_eq!(msg, "Offer expired");
+            }
+            e => panic!("unexpected error, {:?}", e),
+        }
+        assert_eq!(error.to_string(), "Contract error 42: Offer expired");
+    }
+
     #[test]
     fn invalid_base64_works_for_strings() {
         let error = StdError::invalid_base64("my text");
@@ -363,6 +545,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn overflow_works() {
+        let error = StdError::overflow(OverflowOperation::Mul, 123u128, 456u128);
+        match error {
+            StdError::Overflow {
+                operation,
+                operand1,
+                operand2,
+                ..
+            } => {
+                assert_eq!(operation, OverflowOperation::Mul);
+                assert_eq!(operand1, "123");
+                assert_eq!(operand2, "456");
+            }
+            _ => panic!("expect different error"),
+        }
+        assert_eq!(
+            StdError::overflow(OverflowOperation::Add, 1, 2).to_string(),
+            "Cannot add with 1 and 2"
+        );
+    }
+
+    #[test]
+    fn divide_by_zero_works() {
+        let error = StdError::divide_by_zero(123u128);
+        match &error {
+            StdError::DivideByZero { operand, .. } => assert_eq!(operand, "123"),
+            _ => panic!("expect different error"),
+        }
+        assert_eq!(error.to_string(), "Cannot divide 123 by zero");
+    }
+
+    #[test]
+    fn invalid_payment_works() {
+        let error = StdError::invalid_payment(PaymentError::MissingDenom {
+            denom: "uscrt".to_string(),
+        });
+        match &error {
+            StdError::InvalidPayment {
+                reason: PaymentError::MissingDenom { denom },
+                ..
+            } => assert_eq!(denom, "uscrt"),
+            _ => panic!("expect different error"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Invalid payment: Must send 'uscrt' to pay"
+        );
+    }
+
     #[test]
     fn unauthorized_works() {
         let error = StdError::unauthorized();
@@ -451,6 +683,16 @@ mod test {
         assert_conversion(GenericErr { msg: "something" }.build());
     }
 
+    #[test]
+    fn contract_err_conversion() {
+        assert_conversion(StdError::contract_err(7, "Not enough bids"));
+        let error = StdError::contract_err(7, "Not enough bids");
+        assert_eq!(
+            to_vec(&error).unwrap(),
+            br#"{"contract_err":{"code":7,"msg":"Not enough bids"}}"#.to_vec()
+        );
+    }
+
     #[test]
     fn invalid_base64_conversion() {
         assert_conversion(
@@ -466,6 +708,33 @@ mod test {
         assert_conversion(Unauthorized {}.build());
     }
 
+    #[test]
+    fn overflow_conversion() {
+        assert_conversion(StdError::overflow(OverflowOperation::Pow, 2u128, 300u128));
+        let error = StdError::overflow(OverflowOperation::Sub, 1u128, 2u128);
+        assert_eq!(
+            to_vec(&error).unwrap(),
+            br#"{"overflow":{"operation":"sub","operand1":"1","operand2":"2"}}"#.to_vec()
+        );
+    }
+
+    #[test]
+    fn divide_by_zero_conversion() {
+        assert_conversion(StdError::divide_by_zero(42u128));
+    }
+
+    #[test]
+    fn invalid_payment_conversion() {
+        assert_conversion(StdError::invalid_payment(PaymentError::NoFunds {}));
+        let error = StdError::invalid_payment(PaymentError::ExtraDenom {
+            denom: "uatom".to_string(),
+        });
+        assert_eq!(
+            to_vec(&error).unwrap(),
+            br#"{"invalid_payment":{"reason":{"extra_denom":{"denom":"uatom"}}}}"#.to_vec()
+        );
+    }
+
     #[test]
     fn not_found_conversion() {
         assert_conversion(NotFound { kind: "State" }.build());
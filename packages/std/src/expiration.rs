@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::errors::{StdError, StdResult};
+use crate::timestamp::Timestamp;
+use crate::types::BlockInfo;
+
+/// Expiration is the point after which something (e.g. an allowance or an offer) is no
+/// longer valid, either a block height or a block time.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Expiration {
+    /// Expires once this block height is reached
+    AtHeight(u64),
+    /// Expires once this block time is reached
+    AtTime(Timestamp),
+    /// Never expires
+    Never {},
+}
+
+impl Default for Expiration {
+    fn default() -> Self {
+        Expiration::Never {}
+    }
+}
+
+impl Expiration {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self {
+            Expiration::AtHeight(height) => block.height >= *height,
+            Expiration::AtTime(time) => block.timestamp() >= *time,
+            Expiration::Never {} => false,
+        }
+    }
+}
+
+impl fmt::Display for Expiration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expiration::AtHeight(height) => write!(f, "expiration height: {}", height),
+            Expiration::AtTime(time) => write!(f, "expiration time: {}", time),
+            Expiration::Never {} => write!(f, "expiration: never"),
+        }
+    }
+}
+
+/// Expirations of the same kind are ordered, Never comes after all others.
+/// Heights and times cannot be compared.
+impl PartialOrd for Expiration {
+    fn partial_cmp(&self, other: &Expiration) -> Option<Ordering> {
+        match (self, other) {
+            (Expiration::AtHeight(height1), Expiration::AtHeight(height2)) => {
+                Some(height1.cmp(height2))
+            }
+            (Expiration::AtTime(time1), Expiration::AtTime(time2)) => Some(time1.cmp(time2)),
+            (Expiration::Never {}, Expiration::Never {}) => Some(Ordering::Equal),
+            (Expiration::Never {}, _) => Some(Ordering::Greater),
+            (_, Expiration::Never {}) => Some(Ordering::Less),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a duration of the same kind, e.g. to extend a deadline
+impl Add<Duration> for Expiration {
+    type Output = StdResult<Expiration>;
+
+    fn add(self, duration: Duration) -> StdResult<Expiration> {
+        match (self, duration) {
+            (Expiration::AtHeight(height), Duration::Height(delta)) => height
+                .checked_add(delta)
+                .map(Expiration::AtHeight)
+                .ok_or_else(|| StdError::generic_err("Expiration height overflow")),
+            (Expiration::AtTime(time), Duration::Time(delta)) => delta
+                .checked_mul(1_000_000_000)
+                .and_then(|nanos| time.checked_add_nanos(nanos))
+                .map(Expiration::AtTime)
+                .ok_or_else(|| StdError::generic_err("Expiration time overflow")),
+            (Expiration::Never {}, _) => Ok(Expiration::Never {}),
+            _ => Err(StdError::generic_err(
+                "Cannot add a duration to an expiration of another kind",
+            )),
+        }
+    }
+}
+
+/// Duration is an amount of blocks or of seconds, used to compute an Expiration
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Duration {
+    Height(u64),
+    /// Time in seconds
+    Time(u64),
+}
+
+impl Duration {
+    /// Returns the Expiration this duration after the given block, or an error on overflow
+    pub fn after(&self, block: &BlockInfo) -> StdResult<Expiration> {
+        let now = match self {
+            Duration::Height(_) => Expiration::AtHeight(block.height),
+            Duration::Time(_) => Expiration::AtTime(block.timestamp()),
+        };
+        now + *self
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Duration::Height(height) => write!(f, "height: {}", height),
+            Duration::Time(seconds) => write!(f, "time: {}", seconds),
+        }
+    }
+}
+
+/// Panics on overflow
+impl Mul<u64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, rhs: u64) -> Duration {
+        match self {
+            Duration::Height(height) => Duration::Height(
+                height
+                    .checked_mul(rhs)
+                    .expect("attempt to multiply with overflow"),
+            ),
+            Duration::Time(seconds) => Duration::Time(
+                seconds
+                    .checked_mul(rhs)
+                    .expect("attempt to multiply with overflow"),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::serde::{from_slice, to_vec};
+
+    fn block(height: u64, time: u64) -> BlockInfo {
+        BlockInfo {
+            height,
+            time,
+            chain_id: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn is_expired_works() {
+        let expiration = Expiration::AtHeight(100);
+        assert!(!expiration.is_expired(&block(99, 0)));
+        assert!(expiration.is_expired(&block(100, 0)));
+
+        let expiration = Expiration::AtTime(Timestamp::from_seconds(1000));
+        assert!(!expiration.is_expired(&block(1, 999)));
+        assert!(expiration.is_expired(&block(1, 1000)));
+
+        assert!(!Expiration::Never {}.is_expired(&block(u64::MAX, u64::MAX / 1_000_000_000)));
+        assert_eq!(Expiration::default(), Expiration::Never {});
+    }
+
+    #[test]
+    fn duration_after_works() {
+        let current = block(100, 1000);
+        assert_eq!(
+            Duration::Height(5).after(&current),
+            Ok(Expiration::AtHeight(105))
+        );
+        assert_eq!(
+            Duration::Time(60).after(&current),
+            Ok(Expiration::AtTime(Timestamp::from_seconds(1060)))
+        );
+        assert!(Duration::Height(u64::MAX).after(&current).is_err());
+        assert!(Duration::Time(u64::MAX / 1_000_000_000).after(&current).is_err());
+        assert_eq!(Duration::Time(60) * 2, Duration::Time(120));
+    }
+
+    #[test]
+    fn add_duration_works() {
+        assert_eq!(
+            Expiration::AtHeight(10) + Duration::Height(5),
+            Ok(Expiration::AtHeight(15))
+        );
+        assert_eq!(
+            Expiration::AtTime(Timestamp::from_seconds(10)) + Duration::Time(5),
+            Ok(Expiration::AtTime(Timestamp::from_seconds(15)))
+        );
+        assert_eq!(
+            Expiration::Never {} + Duration::Time(5),
+            Ok(Expiration::Never {})
+        );
+        assert!((Expiration::AtHeight(10) + Duration::Time(5)).is_err());
+        assert!((Expiration::AtHeight(u64::MAX) + Duration::Height(1)).is_err());
+    }
+
+    #[test]
+    fn expiration_ordering() {
+        assert!(Expiration::AtHeight(10) < Expiration::AtHeight(11));
+        assert!(Expiration::AtHeight(10) < Expiration::Never {});
+        assert!(
+            Expiration::AtTime(Timestamp::from_seconds(2))
+                > Expiration::AtTime(Timestamp::from_seconds(1))
+        );
+        assert_eq!(
+            Expiration::AtHeight(10).partial_cmp(&Expiration::AtTime(Timestamp::from_seconds(1))),
+            None
+        );
+    }
+
+    #[test]
+    fn expiration_serialization() {
+        let expiration = Expiration::AtTime(Timestamp::from_seconds(1));
+        let serialized = to_vec(&expiration).unwrap();
+        assert_eq!(serialized, br#"{"at_time":"1000000000"}"#.to_vec());
+        assert_eq!(from_slice::<Expiration>(&serialized).unwrap(), expiration);
+
+        let serialized = to_vec(&Expiration::Never {}).unwrap();
+        assert_eq!(serialized, br#"{"never":{}}"#.to_vec());
+        let serialized = to_vec(&Duration::Height(7)).unwrap();
+        assert_eq!(serialized, br#"{"height":7}"#.to_vec());
+    }
+}
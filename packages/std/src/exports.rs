@@ -1,27 +1,36 @@
 //! exports exposes the public wasm API
 //!
-//! wasm2_vm_version_1, allocate and deallocate turn into Wasm exports
+//! wasm2_vm_version_4, allocate and deallocate turn into Wasm exports
 //! as soon as wasm2_std is `use`d in the contract, even privately.
 //!
 //! do_handle and do_wrapper should be wrapped with a extern "C" entry point
 //! including the contract-specific init/handle function pointer.
+//!
+//! The contract functions may fail with their own error type, as long as it converts into
+//! StdError (e.g. to `StdError::ContractErr` for typed error codes), which is what crosses
+//! the wasm boundary.
 use std::fmt;
 use std::vec::Vec;
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::errors::StdResult;
+use crate::errors::StdError;
 use crate::imports::{ExternalApi, ExternalQuerier, ExternalStorage};
 use crate::memory::{alloc, consume_region, release_buffer, Region};
+use crate::panic::install_panic_handler;
 use crate::serde::{from_slice, to_vec};
 use crate::traits::Extern;
-use crate::{Env, HandleResult, QueryResponse, QueryResult};
+use crate::{Env, HandleResponse, HandleResult, QueryResponse, QueryResult, Reply};
 
 /// wasm2_vm_version_* exports mark which Wasm VM interface level this contract is compiled for.
 /// They can be checked by the WASM2 engine.
 /// Update this whenever the Wasm VM interface breaks.
+///
+/// Version 2: the query export receives the Env as well (`query(env_ptr, msg_ptr)`).
+/// Version 3: adds the `addr_validate` import.
+/// Version 4: adds the `abort` import, used by the panic handler.
 #[no_mangle]
-extern "C" fn wasm2_vm_version_1() -> () {}
+extern "C" fn wasm2_vm_version_4() -> () {}
 
 /// allocate reserves the given number of bytes in wasm memory and returns a pointer
 /// to a Region defining this data. This space is managed by the calling process
@@ -40,50 +49,106 @@ extern "C" fn deallocate(pointer: u32) {
 }
 
 /// do_handle should be wrapped in an external "C" export, containing a contract-specific function as arg
-pub fn do_handle<T, U>(
+pub fn do_handle<T, U, E>(
     handle_fn: &dyn Fn(
         &mut Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
         Env,
         T,
-    ) -> HandleResult<U>,
+    ) -> Result<HandleResponse<U>, E>,
     env_ptr: u32,
     msg_ptr: u32,
 ) -> u32
 where
     T: DeserializeOwned,
     U: Serialize + Clone + fmt::Debug + PartialEq,
+    E: Into<StdError>,
 {
+    install_panic_handler();
     let res: HandleResult<U> =
         _do_handle(handle_fn, env_ptr as *mut Region, msg_ptr as *mut Region);
     let v = to_vec(&res).unwrap();
     release_buffer(v) as u32
 }
 
+/// do_sudo should be wrapped in an external "C" export, containing a contract-specific function as arg.
+/// The export is optional and is only called by the runtime, with a SudoMsg or a contract defined message.
+pub fn do_sudo<T, U, E>(
+    sudo_fn: &dyn Fn(
+        &mut Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
+        Env,
+        T,
+    ) -> Result<HandleResponse<U>, E>,
+    env_ptr: u32,
+    msg_ptr: u32,
+) -> u32
+where
+    T: DeserializeOwned,
+    U: Serialize + Clone + fmt::Debug + PartialEq,
+    E: Into<StdError>,
+{
+    install_panic_handler();
+    // sudo has the same shape as handle, only the caller differs
+    let res: HandleResult<U> =
+        _do_handle(sudo_fn, env_ptr as *mut Region, msg_ptr as *mut Region);
+    let v = to_vec(&res).unwrap();
+    release_buffer(v) as u32
+}
+
+/// do_reply should be wrapped in an external "C" export, containing a contract-specific function as arg.
+/// The export is optional and is called by the runtime with the result of a SubMsg, according to its `reply_on`.
+pub fn do_reply<U, E>(
+    reply_fn: &dyn Fn(
+        &mut Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
+        Env,
+        Reply,
+    ) -> Result<HandleResponse<U>, E>,
+    env_ptr: u32,
+    msg_ptr: u32,
+) -> u32
+where
+    U: Serialize + Clone + fmt::Debug + PartialEq,
+    E: Into<StdError>,
+{
+    install_panic_handler();
+    let res: HandleResult<U> =
+        _do_handle(reply_fn, env_ptr as *mut Region, msg_ptr as *mut Region);
+    let v = to_vec(&res).unwrap();
+    release_buffer(v) as u32
+}
+
 /// do_query should be wrapped in an external "C" export, containing a contract-specific function as arg
-pub fn do_query<T: DeserializeOwned>(
+pub fn do_query<T, E>(
     query_fn: &dyn Fn(
         &Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
+        Env,
         T,
-    ) -> StdResult<QueryResponse>,
+    ) -> Result<QueryResponse, E>,
+    env_ptr: u32,
     msg_ptr: u32,
-) -> u32 {
-    let res: QueryResult = _do_query(query_fn, msg_ptr as *mut Region);
+) -> u32
+where
+    T: DeserializeOwned,
+    E: Into<StdError>,
+{
+    install_panic_handler();
+    let res: QueryResult = _do_query(query_fn, env_ptr as *mut Region, msg_ptr as *mut Region);
     let v = to_vec(&res).unwrap();
     release_buffer(v) as u32
 }
 
-fn _do_handle<T, U>(
+fn _do_handle<T, U, E>(
     handle_fn: &dyn Fn(
         &mut Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
         Env,
         T,
-    ) -> HandleResult<U>,
+    ) -> Result<HandleResponse<U>, E>,
     env_ptr: *mut Region,
     msg_ptr: *mut Region,
 ) -> HandleResult<U>
 where
     T: DeserializeOwned,
     U: Serialize + Clone + fmt::Debug + PartialEq,
+    E: Into<StdError>,
 {
     let env: Vec<u8> = unsafe { consume_region(env_ptr) };
     let msg: Vec<u8> = unsafe { consume_region(msg_ptr) };
@@ -91,21 +156,29 @@ where
     let env: Env = from_slice(&env)?;
     let msg: T = from_slice(&msg)?;
     let mut deps = make_dependencies();
-    handle_fn(&mut deps, env, msg)
+    handle_fn(&mut deps, env, msg).map_err(Into::into)
 }
 
-fn _do_query<T: DeserializeOwned>(
+fn _do_query<T, E>(
     query_fn: &dyn Fn(
         &Extern<ExternalStorage, ExternalApi, ExternalQuerier>,
+        Env,
         T,
-    ) -> StdResult<QueryResponse>,
+    ) -> Result<QueryResponse, E>,
+    env_ptr: *mut Region,
     msg_ptr: *mut Region,
-) -> StdResult<QueryResponse> {
+) -> QueryResult
+where
+    T: DeserializeOwned,
+    E: Into<StdError>,
+{
+    let env: Vec<u8> = unsafe { consume_region(env_ptr) };
     let msg: Vec<u8> = unsafe { consume_region(msg_ptr) };
 
+    let env: Env = from_slice(&env)?;
     let msg: T = from_slice(&msg)?;
     let deps = make_dependencies();
-    query_fn(&deps, msg)
+    query_fn(&deps, env, msg).map_err(Into::into)
 }
 
 /// Makes all bridges to external dependencies (i.e. Wasm imports) that are injected by the VM
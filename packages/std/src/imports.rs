@@ -1,6 +1,6 @@
 use std::vec::Vec;
 
-use crate::addresses::{CanonicalAddr, HumanAddr};
+use crate::addresses::{Addr, CanonicalAddr, HumanAddr};
 use crate::encoding::Binary;
 use crate::errors::{StdError, StdResult};
 #[cfg(feature = "iterator")]
@@ -30,6 +30,9 @@ extern "C" {
 
     fn canonicalize_address(source: u32, destination: u32) -> u32;
     fn humanize_address(source: u32, destination: u32) -> u32;
+    /// Returns 0 if the address is valid, or a pointer to a Region holding the error message.
+    /// Available from VM interface version 3.
+    fn addr_validate(source: u32) -> u32;
 
     /// Executes a query on the chain (import). Not to be confused with the
     /// query export, which queries the state of the contract.
@@ -187,6 +190,22 @@ impl Api for ExternalApi {
         let address = unsafe { consume_string_region_written_by_vm(human) };
         Ok(address.into())
     }
+
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        let send = build_region(human.as_bytes());
+        let send_ptr = &*send as *const Region as u32;
+
+        let result = unsafe { addr_validate(send_ptr) };
+        if result != 0 {
+            let error = unsafe { consume_string_region_written_by_vm(result as *mut Region) };
+            return Err(StdError::generic_err(format!(
+                "addr_validate errored: {}",
+                error
+            )));
+        }
+
+        Ok(Addr::unchecked(human))
+    }
 }
 
 /// Takes a pointer to a Region and reads the data into a String.
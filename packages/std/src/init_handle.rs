@@ -7,9 +7,11 @@ use crate::addresses::HumanAddr;
 use crate::coins::Coin;
 use crate::encoding::Binary;
 use crate::errors::{StdResult};
+use crate::submessages::SubMsg;
 use crate::types::Empty;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 // See https://github.com/serde-rs/serde/issues/1296 why we cannot add De-Serialize trait bounds to T
 pub enum CosmosMsg<T = Empty>
@@ -26,6 +28,7 @@ where
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum GovMsg {
     // Let contract vote on a governance proposal
@@ -36,6 +39,7 @@ pub enum GovMsg {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 // don't use rename_all here or you will break this
 pub enum VoteOption {
     Yes,
@@ -45,6 +49,7 @@ pub enum VoteOption {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum BankMsg {
     // this moves tokens in the underlying sdk
@@ -56,6 +61,7 @@ pub enum BankMsg {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum StakingMsg {
     Delegate {
@@ -84,6 +90,7 @@ pub enum StakingMsg {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WasmMsg {
     /// this dispatches a call to another contract at a known address (with known ABI)
@@ -136,6 +143,7 @@ impl<T: Clone + fmt::Debug + PartialEq> From<WasmMsg> for CosmosMsg<T> {
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LogAttribute {
     pub key: String,
     pub value: String,
@@ -160,13 +168,53 @@ pub fn plaintext_log<K: ToString, V: ToString>(key: K, value: V) -> LogAttribute
     }
 }
 
+/// Event groups the attributes of one action of a contract under a type, so indexers can tell
+/// which action produced which attributes. Each attribute keeps its own `encrypted` flag.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Event {
+    /// The event type, e.g. "transfer". The host prefixes it with "wasm-".
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub attributes: Vec<LogAttribute>,
+}
+
+impl Event {
+    pub fn new<T: Into<String>>(ty: T) -> Self {
+        Event {
+            ty: ty.into(),
+            attributes: vec![],
+        }
+    }
+
+    /// Adds an encrypted attribute, like `log`
+    pub fn add_attribute<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.attributes.push(log(key, value));
+        self
+    }
+
+    /// Adds a plaintext attribute, like `plaintext_log`
+    pub fn add_plaintext_attribute<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.attributes.push(plaintext_log(key, value));
+        self
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HandleResponse<T = Empty>
 where
     T: Clone + fmt::Debug + PartialEq,
 {
     pub messages: Vec<CosmosMsg<T>>,
+    /// Messages whose result is sent back to the `reply` entry point, according to their `reply_on`.
+    /// They are executed after `messages`, in order.
+    #[serde(default = "Vec::<SubMsg<T>>::new")]
+    pub submessages: Vec<SubMsg<T>>,
     pub log: Vec<LogAttribute>,
+    /// Structured events, in addition to the flat `log`
+    #[serde(default)]
+    pub events: Vec<Event>,
     pub data: Option<Binary>,
 }
 
@@ -179,7 +227,9 @@ where
     fn default() -> Self {
         HandleResponse {
             messages: vec![],
+            submessages: vec![],
             log: vec![],
+            events: vec![],
             data: None,
         }
     }
@@ -192,7 +242,9 @@ where
     fn from(ctx: Context<T>) -> Self {
         HandleResponse {
             messages: ctx.messages,
+            submessages: ctx.submessages,
             log: ctx.log,
+            events: ctx.events,
             data: ctx.data,
         }
     }
@@ -204,7 +256,9 @@ where
     T: Clone + fmt::Debug + PartialEq,
 {
     messages: Vec<CosmosMsg<T>>,
+    submessages: Vec<SubMsg<T>>,
     log: Vec<LogAttribute>,
+    events: Vec<Event>,
     data: Option<Binary>,
 }
 
@@ -215,7 +269,9 @@ where
     fn default() -> Self {
         Context {
             messages: vec![],
+            submessages: vec![],
             log: vec![],
+            events: vec![],
             data: None,
         }
     }
@@ -241,6 +297,14 @@ where
         self.messages.push(msg.into());
     }
 
+    pub fn add_submessage(&mut self, msg: SubMsg<T>) {
+        self.submessages.push(msg);
+    }
+
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
     pub fn set_data<U: Into<Binary>>(&mut self, data: U) {
         self.data = Some(data.into());
     }
@@ -316,6 +380,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn handle_response_without_submessages_deserializes() {
+        let response: HandleResponse =
+            from_slice(br#"{"messages":[],"log":[],"data":null}"#).unwrap();
+        assert_eq!(response, HandleResponse::default());
+    }
+
+    #[test]
+    fn event_serializes_with_type_and_attributes() {
+        let event = Event::new("transfer")
+            .add_attribute("amount", 42)
+            .add_plaintext_attribute("recipient", "you");
+        assert_eq!(
+            to_vec(&event).unwrap(),
+            br#"{"type":"transfer","attributes":[{"key":"amount","value":"42","encrypted":true},{"key":"recipient","value":"you","encrypted":false}]}"#.to_vec()
+        );
+
+        let mut ctx: Context = Context::new();
+        ctx.add_event(event.clone());
+        let response: HandleResponse = ctx.into();
+        assert_eq!(response.events, vec![event]);
+        assert_eq!(response.log, vec![]);
+    }
+
     #[test]
     fn empty_context() {
         let ctx = Context::new();
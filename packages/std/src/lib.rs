@@ -1,41 +1,64 @@
 // Exposed on all platforms
 
 mod addresses;
+mod bech32;
+mod codec;
 mod coins;
 mod debug_print;
 mod encoding;
 mod entry_points;
 mod errors;
+mod expiration;
 mod init_handle;
 #[cfg(feature = "iterator")]
 mod iterator;
 mod math;
+mod payment;
 mod query;
+#[cfg(feature = "schema")]
+mod schema;
 mod serde;
 mod storage;
+mod submessages;
+mod sudo;
+mod timestamp;
 mod traits;
 mod types;
 
-pub use crate::addresses::{CanonicalAddr, HumanAddr};
-pub use crate::coins::{coin, coins, has_coins, Coin};
+pub use crate::addresses::{Addr, CanonicalAddr, HumanAddr};
+pub use crate::bech32::{bech32_decode, bech32_encode, Bech32Api};
+#[cfg(feature = "bincode")]
+pub use crate::codec::Bincode;
+pub use crate::codec::{Codec, Json};
+pub use crate::coins::{coin, coins, has_coins, Coin, Coins};
 pub use crate::debug_print::debug_print;
 pub use crate::encoding::Binary;
-pub use crate::errors::{StdError, StdResult, SystemError, SystemResult};
+pub use crate::errors::{
+    OverflowOperation, PaymentError, StdError, StdResult, SystemError, SystemResult,
+};
+pub use crate::expiration::{Duration, Expiration};
 pub use crate::init_handle::{
-    log, plaintext_log, BankMsg, Context, CosmosMsg, GovMsg, HandleResponse, HandleResult,
+    log, plaintext_log, BankMsg, Context, CosmosMsg, Event, GovMsg, HandleResponse, HandleResult,
     LogAttribute,  StakingMsg, VoteOption,  WasmMsg,
 };
 #[cfg(feature = "iterator")]
 pub use crate::iterator::{Order, KV};
-pub use crate::math::{Decimal, Uint128};
+pub use crate::math::{
+    Decimal, Decimal256, Int128, SignedDecimal, Uint128, Uint256, Uint512,
+};
+pub use crate::payment::{may_pay, must_pay, must_pay_at_least, nonpayable};
 pub use crate::query::{
     AllBalanceResponse, AllDelegationsResponse, BalanceResponse, BankQuery, BondedDenomResponse,
     BondedRatioResponse, Delegation, DistQuery, FullDelegation, GovQuery, InflationResponse,
-    MintQuery, ProposalsResponse, QueryRequest, QueryResponse, QueryResult, RewardsResponse,
-    StakingQuery, UnbondingDelegationsResponse, Validator, ValidatorsResponse, WasmQuery,
+    MintQuery, Proposal, ProposalsResponse, QueryRequest, QueryResponse, QueryResult,
+    RewardsResponse, StakingQuery, UnbondingDelegationsResponse, Validator, ValidatorRewards,
+    ValidatorsResponse, WasmQuery,
 };
 pub use crate::serde::{from_binary, from_slice, to_binary, to_vec};
 pub use crate::storage::MemoryStorage;
+pub use crate::submessages::{Reply, ReplyOn, SubMsg, SubMsgExecutionResponse, SubMsgResult};
+pub use crate::sudo::SudoMsg;
+pub use crate::timestamp::Timestamp;
 pub use crate::traits::{Api, Extern, Querier, QuerierResult, ReadonlyStorage, Storage};
 pub use crate::types::{BlockInfo, ContractInfo, Empty, Env, MessageInfo};
 
@@ -46,12 +69,14 @@ mod exports;
 #[cfg(target_arch = "wasm32")]
 mod imports;
 #[cfg(target_arch = "wasm32")]
+mod panic;
+#[cfg(target_arch = "wasm32")]
 pub mod memory; // Used by exports and imports only. This assumes pointers are 32 bit long, which makes it untestable on dev machines.
 
 // TODO: REMOVE PUB MEMORY
 
 #[cfg(target_arch = "wasm32")]
-pub use crate::exports::{do_handle, do_query};
+pub use crate::exports::{do_handle, do_query, do_reply, do_sudo};
 #[cfg(target_arch = "wasm32")]
 pub use crate::imports::{ExternalApi, ExternalQuerier, ExternalStorage};
 
@@ -61,10 +86,18 @@ pub use crate::imports::{ExternalApi, ExternalQuerier, ExternalStorage};
 #[cfg(not(target_arch = "wasm32"))]
 mod mock;
 #[cfg(not(target_arch = "wasm32"))]
+mod simulator;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod testing {
     pub use crate::mock::{
-        mock_dependencies, mock_dependencies_with_balances, mock_env, BankQuerier, MockApi,
-        MockQuerier, MockQuerierCustomHandlerResult, MockStorage, StakingQuerier,
+        mock_dependencies, mock_dependencies_with_balances, mock_env, BankQuerier, DistQuerier,
+        GovQuerier, MintQuerier, MockApi, MockContractQuery, MockCustomHandler,
+        MockDependenciesBuilder, MockEnvBuilder, MockQuerier, MockQuerierCustomHandlerResult,
+        MockStorage, MockWasmHandler, StakingQuerier, WasmQuerier, MOCK_BLOCK_TIME,
         MOCK_CONTRACT_ADDR,
     };
+    pub use crate::simulator::{
+        parse_data, ChainDeps, ChainQuerier, ChainResponse, ContractStorage, ContractWrapper,
+        MockChain, MockChainContract,
+    };
 }
@@ -1,10 +1,12 @@
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Write};
+use std::iter::Sum;
+use std::marker::PhantomData;
 use std::ops;
 use std::str::FromStr;
 
-use crate::errors::{StdError, StdResult};
+use crate::errors::{OverflowOperation, StdError, StdResult};
 
 /// A fixed-point decimal value with 18 fractional digits, i.e. Decimal(1_000_000_000_000_000_000) == 1.0
 ///
@@ -37,98 +39,1899 @@ impl Decimal {
         Decimal((x as u128) * 1_000_000_000_000_000)
     }
 
-    /// Returns the ratio (nominator / denominator) as a Decimal
+    /// Returns the ratio (nominator / denominator) as a Decimal, rounded down.
+    /// Panics if the denominator is zero or the ratio does not fit into a Decimal.
     pub fn from_ratio<A: Into<u128>, B: Into<u128>>(nominator: A, denominator: B) -> Decimal {
+        match Decimal::checked_from_ratio(nominator, denominator) {
+            Ok(value) => value,
+            Err(StdError::DivideByZero { .. }) => panic!("Denominator must not be zero"),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the ratio (nominator / denominator) as a Decimal, rounded down.
+    /// The ratio is computed in 256 bits, so no precision is lost on large nominators.
+    pub fn checked_from_ratio<A: Into<u128>, B: Into<u128>>(
+        nominator: A,
+        denominator: B,
+    ) -> StdResult<Decimal> {
         let nominator: u128 = nominator.into();
         let denominator: u128 = denominator.into();
         if denominator == 0 {
-            panic!("Denominator must not be zero");
+            return Err(StdError::divide_by_zero(nominator));
+        }
+        let atomics = Uint256::from(nominator) * Uint256::from(DECIMAL_FRACTIONAL)
+            / Uint256::from(denominator);
+        u128::try_from(atomics).map(Decimal).map_err(|_| {
+            conversion_overflow("ratio", "Decimal", format!("{}/{}", nominator, denominator))
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Decimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Add, self, other))
+    }
+
+    pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Decimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, self, other))
+    }
+
+    /// Multiplies two decimals, the result is rounded down to 18 decimal places
+    pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+        let product =
+            Uint256::from(self.0) * Uint256::from(other.0) / Uint256::from(DECIMAL_FRACTIONAL);
+        u128::try_from(product)
+            .map(Decimal)
+            .map_err(|_| StdError::overflow(OverflowOperation::Mul, self, other))
+    }
+
+    /// Divides two decimals, the result is rounded down to 18 decimal places
+    pub fn checked_div(self, other: Self) -> StdResult<Self> {
+        if other.is_zero() {
+            return Err(StdError::divide_by_zero(self));
+        }
+        let quotient =
+            Uint256::from(self.0) * Uint256::from(DECIMAL_FRACTIONAL) / Uint256::from(other.0);
+        u128::try_from(quotient)
+            .map(Decimal)
+            .map_err(|_| StdError::overflow(OverflowOperation::Div, self, other))
+    }
+
+    /// Raises the decimal to the given power by squaring, every step is rounded down
+    pub fn checked_pow(self, exp: u32) -> StdResult<Self> {
+        let overflow =
+            |_| StdError::overflow(OverflowOperation::Pow, self.to_string(), exp.to_string());
+
+        let mut base = self;
+        let mut exp_left = exp;
+        let mut result = Decimal::one();
+        while exp_left > 0 {
+            if exp_left % 2 == 1 {
+                result = result.checked_mul(base).map_err(overflow)?;
+            }
+            exp_left /= 2;
+            if exp_left > 0 {
+                base = base.checked_mul(base).map_err(overflow)?;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("attempt to raise to a power with overflow")
+    }
+
+    /// Returns the square root, rounded down to 18 decimal places
+    pub fn sqrt(&self) -> Self {
+        // sqrt(x / 10^18) * 10^18 == sqrt(x * 10^18)
+        let scaled = Uint256::from(self.0) * Uint256::from(DECIMAL_FRACTIONAL);
+        Decimal(
+            u128::try_from(Uint256(scaled.0.integer_sqrt()))
+                .expect("the square root of a Decimal always fits into a Decimal"),
+        )
+    }
+
+    /// Returns 1 / self, rounded down to 18 decimal places, or None for zero
+    pub fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Decimal(DECIMAL_FRACTIONAL * DECIMAL_FRACTIONAL / self.0))
+        }
+    }
+
+    /// Rounds down to an integer
+    pub fn floor(&self) -> Self {
+        self.floor_to(0)
+    }
+
+    /// Rounds up to an integer, panics if the result does not fit into a Decimal
+    pub fn ceil(&self) -> Self {
+        self.ceil_to(0)
+    }
+
+    /// Rounds to the nearest integer, halves are rounded up
+    pub fn round(&self) -> Self {
+        self.round_to(0)
+    }
+
+    /// Rounds down to the given number of decimal places
+    pub fn floor_to(&self, places: u32) -> Self {
+        let unit = Decimal::rounding_unit(places);
+        Decimal(self.0 - self.0 % unit)
+    }
+
+    /// Rounds up to the given number of decimal places
+    pub fn checked_ceil_to(&self, places: u32) -> StdResult<Self> {
+        let unit = Decimal::rounding_unit(places);
+        match self.0 % unit {
+            0 => Ok(*self),
+            remainder => self.checked_add(Decimal(unit - remainder)),
+        }
+    }
+
+    /// Rounds up to the given number of decimal places, panics if the result does not fit into a Decimal
+    pub fn ceil_to(&self, places: u32) -> Self {
+        self.checked_ceil_to(places)
+            .expect("attempt to round up with overflow")
+    }
+
+    /// Rounds to the nearest value with the given number of decimal places, halves are rounded up
+    pub fn round_to(&self, places: u32) -> Self {
+        let unit = Decimal::rounding_unit(places);
+        if self.0 % unit >= unit - unit / 2 {
+            self.ceil_to(places)
+        } else {
+            self.floor_to(places)
+        }
+    }
+
+    /// Returns the integer part
+    pub fn to_uint_floor(&self) -> Uint128 {
+        Uint128(self.0 / DECIMAL_FRACTIONAL)
+    }
+
+    /// Returns the smallest integer greater than or equal to self
+    pub fn to_uint_ceil(&self) -> Uint128 {
+        let whole = self.0 / DECIMAL_FRACTIONAL;
+        if self.0.is_multiple_of(DECIMAL_FRACTIONAL) {
+            Uint128(whole)
+        } else {
+            Uint128(whole + 1)
+        }
+    }
+
+    /// Returns the nearest integer, halves are rounded up
+    pub fn to_uint_round(&self) -> Uint128 {
+        let whole = self.0 / DECIMAL_FRACTIONAL;
+        if self.0 % DECIMAL_FRACTIONAL >= DECIMAL_FRACTIONAL / 2 {
+            Uint128(whole + 1)
+        } else {
+            Uint128(whole)
+        }
+    }
+
+    /// The atomics step of a value rounded to the given number of decimal places
+    fn rounding_unit(places: u32) -> u128 {
+        10u128.pow(18 - places.min(18))
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = StdError;
+
+    /// Converts the decimal string to a Decimal
+    /// Possible inputs: "1.23", "1", "000012", "1.123000000"
+    /// Disallowed: "", ".23"
+    ///
+    /// This never performs any kind of rounding.
+    /// More than 18 fractional digits, even zeros, result in an error.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (whole, fractional) = parse_decimal_str(input, |whole| whole.parse::<u128>().ok())?;
+        let atomics = whole
+            .checked_mul(DECIMAL_FRACTIONAL)
+            .and_then(|whole_as_atomics| whole_as_atomics.checked_add(fractional))
+            .ok_or_else(|| StdError::generic_err("Value too big"))?;
+        Ok(Decimal(atomics))
+    }
+}
+
+/// Splits a decimal string into its whole part, parsed with parse_whole, and its fractional part
+/// as atomics (units of 10^-18). This is the format shared by all the decimal types.
+fn parse_decimal_str<T>(
+    input: &str,
+    parse_whole: impl Fn(&str) -> Option<T>,
+) -> StdResult<(T, u128)> {
+    // the integer parsers accept a leading plus sign, which is not part of the decimal format
+    if input.starts_with('+') || input.contains(".+") {
+        return Err(StdError::generic_err("Unexpected plus sign"));
+    }
+    let parts: Vec<&str> = input.split('.').collect();
+    match parts.len() {
+        1 => {
+            let whole = parse_whole(parts[0])
+                .ok_or_else(|| StdError::generic_err("Error parsing whole"))?;
+            Ok((whole, 0))
+        }
+        2 => {
+            let whole = parse_whole(parts[0])
+                .ok_or_else(|| StdError::generic_err("Error parsing whole"))?;
+            let fractional = parts[1]
+                .parse::<u128>()
+                .map_err(|_| StdError::generic_err("Error parsing fractional"))?;
+            let exp = (18usize.checked_sub(parts[1].len())).ok_or_else(|| {
+                StdError::generic_err("Cannot parse more than 18 fractional digits")
+            })?;
+            let fractional_factor = 10u128
+                .checked_pow(exp.try_into().unwrap())
+                .ok_or_else(|| StdError::generic_err("Cannot compute fractional factor"))?;
+            Ok((whole, fractional * fractional_factor))
+        }
+        _ => Err(StdError::generic_err("Unexpected number of dots")),
+    }
+}
+
+/// Writes a decimal from its whole part and its fractional part in atomics, without trailing zeros
+fn write_decimal(
+    f: &mut fmt::Formatter,
+    whole: &dyn fmt::Display,
+    fractional: u128,
+) -> fmt::Result {
+    if fractional == 0 {
+        write!(f, "{}", whole)
+    } else {
+        let fractional_string = format!("{:018}", fractional);
+        f.write_str(&whole.to_string())?;
+        f.write_char('.')?;
+        f.write_str(fractional_string.trim_end_matches('0'))?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let whole = (self.0) / DECIMAL_FRACTIONAL;
+        let fractional = (self.0) % DECIMAL_FRACTIONAL;
+        write_decimal(f, &whole, fractional)
+    }
+}
+
+impl ops::Add for Decimal {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl ops::AddAssign for Decimal {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for Decimal {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl ops::SubAssign for Decimal {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Mul for Decimal {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl ops::MulAssign for Decimal {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for Decimal {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        match self.checked_div(other) {
+            Ok(quotient) => quotient,
+            Err(StdError::DivideByZero { .. }) => panic!("attempt to divide by zero"),
+            Err(_) => panic!("attempt to divide with overflow"),
         }
-        // TODO: better algorithm with less rounding potential?
-        Decimal(nominator * DECIMAL_FRACTIONAL / denominator)
+    }
+}
+
+impl ops::DivAssign for Decimal {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl Sum<Decimal> for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Self {
+        iter.fold(Decimal::zero(), ops::Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Serializes as a decimal string
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes as a base64 string
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FromStrVisitor::<Decimal>::new("Decimal"))
+    }
+}
+
+//*** Uint128 ***/
+/// A 128 bit unsigned integer, serialized as a string in JSON so it is not truncated by clients.
+///
+/// The `+`, `*`, `/` and `%` operators panic on overflow and division by zero, also in release
+/// builds. Use the `checked_*` methods to get an `StdError` instead, or the `saturating_*` and
+/// `wrapping_*` methods for the respective overflow behaviour. `-` returns an `StdResult` for
+/// historical reasons, with the same error as `checked_sub`.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uint128(pub u128);
+
+impl Uint128 {
+    pub const MAX: Uint128 = Uint128(u128::MAX);
+
+    /// Creates a Uint128(0)
+    pub const fn zero() -> Self {
+        Uint128(0)
+    }
+
+    /// Returns a copy of the internal data
+    pub fn u128(&self) -> u128 {
+        self.0
     }
 
     pub fn is_zero(&self) -> bool {
         self.0 == 0
     }
+
+    pub fn checked_add(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Uint128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Add, self, other))
+    }
+
+    pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Uint128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, self, other))
+    }
+
+    pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_mul(other.0)
+            .map(Uint128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Mul, self, other))
+    }
+
+    pub fn checked_div(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_div(other.0)
+            .map(Uint128)
+            .ok_or_else(|| StdError::divide_by_zero(self))
+    }
+
+    pub fn checked_rem(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_rem(other.0)
+            .map(Uint128)
+            .ok_or_else(|| StdError::divide_by_zero(self))
+    }
+
+    pub fn checked_pow(self, exp: u32) -> StdResult<Self> {
+        self.0
+            .checked_pow(exp)
+            .map(Uint128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Pow, self.0, exp.into()))
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Uint128(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Uint128(self.0.saturating_sub(other.0))
+    }
+
+    pub fn saturating_mul(self, other: Self) -> Self {
+        Uint128(self.0.saturating_mul(other.0))
+    }
+
+    /// Division cannot overflow for unsigned integers, so this only panics if other is zero
+    pub fn saturating_div(self, other: Self) -> Self {
+        Uint128(self.0 / other.0)
+    }
+
+    /// Division cannot overflow for unsigned integers, so this only panics if other is zero
+    pub fn saturating_rem(self, other: Self) -> Self {
+        Uint128(self.0 % other.0)
+    }
+
+    pub fn saturating_pow(self, exp: u32) -> Self {
+        Uint128(self.0.saturating_pow(exp))
+    }
+
+    pub fn wrapping_add(self, other: Self) -> Self {
+        Uint128(self.0.wrapping_add(other.0))
+    }
+
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        Uint128(self.0.wrapping_sub(other.0))
+    }
+
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        Uint128(self.0.wrapping_mul(other.0))
+    }
+
+    /// Division cannot overflow for unsigned integers, so this only panics if other is zero
+    pub fn wrapping_div(self, other: Self) -> Self {
+        Uint128(self.0.wrapping_div(other.0))
+    }
+
+    /// Division cannot overflow for unsigned integers, so this only panics if other is zero
+    pub fn wrapping_rem(self, other: Self) -> Self {
+        Uint128(self.0.wrapping_rem(other.0))
+    }
+
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        Uint128(self.0.wrapping_pow(exp))
+    }
+}
+
+impl From<u128> for Uint128 {
+    fn from(val: u128) -> Self {
+        Uint128(val)
+    }
+}
+
+impl From<u64> for Uint128 {
+    fn from(val: u64) -> Self {
+        Uint128(val.into())
+    }
+}
+
+impl From<u32> for Uint128 {
+    fn from(val: u32) -> Self {
+        Uint128(val.into())
+    }
+}
+
+impl TryFrom<Uint128> for u64 {
+    type Error = StdError;
+
+    fn try_from(val: Uint128) -> Result<Self, Self::Error> {
+        u64::try_from(val.0).map_err(|_| conversion_overflow("Uint128", "u64", val))
+    }
+}
+
+impl FromStr for Uint128 {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<u128>() {
+            Ok(u) => Ok(Uint128(u)),
+            Err(e) => Err(StdError::parse_err("Uint128", e)),
+        }
+    }
+}
+
+impl TryFrom<&str> for Uint128 {
+    type Error = StdError;
+
+    fn try_from(val: &str) -> Result<Self, Self::Error> {
+        Uint128::from_str(val)
+    }
+}
+
+impl Into<String> for Uint128 {
+    fn into(self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Into<u128> for Uint128 {
+    fn into(self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for Uint128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ops::Add for Uint128 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Uint128(
+            self.u128()
+                .checked_add(other.u128())
+                .expect("attempt to add with overflow"),
+        )
+    }
+}
+
+impl ops::AddAssign for Uint128 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for Uint128 {
+    type Output = StdResult<Self>;
+
+    fn sub(self, other: Self) -> StdResult<Self> {
+        self.checked_sub(other)
+    }
+}
+
+impl ops::Mul for Uint128 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Uint128(
+            self.u128()
+                .checked_mul(other.u128())
+                .expect("attempt to multiply with overflow"),
+        )
+    }
+}
+
+impl ops::MulAssign for Uint128 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for Uint128 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Uint128(
+            self.u128()
+                .checked_div(other.u128())
+                .expect("attempt to divide by zero"),
+        )
+    }
+}
+
+impl ops::DivAssign for Uint128 {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl ops::Rem for Uint128 {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        Uint128(
+            self.u128()
+                .checked_rem(other.u128())
+                .expect("attempt to calculate the remainder with a divisor of zero"),
+        )
+    }
+}
+
+impl ops::RemAssign for Uint128 {
+    fn rem_assign(&mut self, other: Self) {
+        *self = *self % other;
+    }
+}
+
+impl Sum<Uint128> for Uint128 {
+    fn sum<I: Iterator<Item = Uint128>>(iter: I) -> Self {
+        iter.fold(Uint128::zero(), ops::Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Uint128> for Uint128 {
+    fn sum<I: Iterator<Item = &'a Uint128>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Both d*u and u*d with d: Decimal and u: Uint128 returns an Uint128. There is no
+/// specific reason for this decision other than the initial use cases we have. If you
+/// need a Decimal result for the same calculation, use Decimal(d*u) or Decimal(u*d).
+impl ops::Mul<Decimal> for Uint128 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Decimal) -> Self::Output {
+        // 0*a and b*0 is always 0
+        if self.is_zero() || rhs.is_zero() {
+            return Uint128::zero();
+        }
+        self.multiply_ratio(rhs.0, DECIMAL_FRACTIONAL)
+    }
+}
+
+impl ops::Mul<Uint128> for Decimal {
+    type Output = Uint128;
+
+    fn mul(self, rhs: Uint128) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Uint128 {
+    /// returns self * nom / denom
+    ///
+    /// The product is computed in 256 bits, so this only panics if the final result
+    /// does not fit into a Uint128.
+    pub fn multiply_ratio<A: Into<u128>, B: Into<u128>>(&self, nom: A, denom: B) -> Uint128 {
+        let nominator: u128 = nom.into();
+        let denominator: u128 = denom.into();
+        if denominator == 0 {
+            panic!("Denominator must not be zero");
+        }
+        let val =
+            Uint256::from(self.u128()) * Uint256::from(nominator) / Uint256::from(denominator);
+        Uint128::try_from(val).expect("multiplication overflow")
+    }
+}
+
+/// Serializes as a base64 string
+impl Serialize for Uint128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes as a base64 string
+impl<'de> Deserialize<'de> for Uint128 {
+    fn deserialize<D>(deserializer: D) -> Result<Uint128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FromStrVisitor::<Uint128>::new("Uint128"))
+    }
+}
+
+//*** Uint256 and Uint512 ***/
+#[allow(clippy::all)]
+mod uints {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+
+    uint::construct_uint! {
+        pub struct U512(8);
+    }
+}
+
+use uints::{U256, U512};
+
+/// A 256 bit unsigned integer, serialized as a string in JSON like Uint128.
+///
+/// The operators panic on overflow, underflow and division by zero. Use the `checked_*`
+/// methods to get an `StdError` instead.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uint256(U256);
+
+/// A 512 bit unsigned integer, serialized as a string in JSON like Uint128.
+///
+/// It is mostly useful as an intermediate value to multiply two Uint256 without overflow.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uint512(U512);
+
+fn conversion_overflow<T: fmt::Display>(source: &str, target: &str, value: T) -> StdError {
+    StdError::generic_err(format!("Cannot convert {} {} to {}", source, value, target))
+}
+
+/// Implements the arithmetic, conversions and serde shared by the big unsigned integers.
+/// $bytes is the size of the type in bytes.
+macro_rules! impl_big_uint {
+    ($name:ident, $inner:ident, $bytes:expr) => {
+        impl $name {
+            pub const MAX: $name = $name($inner::MAX);
+
+            pub const fn zero() -> Self {
+                $name($inner::zero())
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            pub fn from_be_bytes(bytes: [u8; $bytes]) -> Self {
+                $name($inner::from_big_endian(&bytes))
+            }
+
+            pub fn to_be_bytes(self) -> [u8; $bytes] {
+                let mut bytes = [0u8; $bytes];
+                self.0.to_big_endian(&mut bytes);
+                bytes
+            }
+
+            pub fn from_le_bytes(bytes: [u8; $bytes]) -> Self {
+                $name($inner::from_little_endian(&bytes))
+            }
+
+            pub fn to_le_bytes(self) -> [u8; $bytes] {
+                let mut bytes = [0u8; $bytes];
+                self.0.to_little_endian(&mut bytes);
+                bytes
+            }
+
+            pub fn checked_add(self, other: Self) -> StdResult<Self> {
+                self.0
+                    .checked_add(other.0)
+                    .map($name)
+                    .ok_or_else(|| StdError::overflow(OverflowOperation::Add, self, other))
+            }
+
+            pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+                self.0
+                    .checked_sub(other.0)
+                    .map($name)
+                    .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, self, other))
+            }
+
+            pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+                self.0
+                    .checked_mul(other.0)
+                    .map($name)
+                    .ok_or_else(|| StdError::overflow(OverflowOperation::Mul, self, other))
+            }
+
+            pub fn checked_div(self, other: Self) -> StdResult<Self> {
+                self.0
+                    .checked_div(other.0)
+                    .map($name)
+                    .ok_or_else(|| StdError::divide_by_zero(self))
+            }
+
+            pub fn checked_rem(self, other: Self) -> StdResult<Self> {
+                self.0
+                    .checked_rem(other.0)
+                    .map($name)
+                    .ok_or_else(|| StdError::divide_by_zero(self))
+            }
+
+            pub fn checked_pow(self, exp: u32) -> StdResult<Self> {
+                self.0
+                    .checked_pow($inner::from(exp))
+                    .map($name)
+                    .ok_or_else(|| {
+                        StdError::overflow(
+                            OverflowOperation::Pow,
+                            self.to_string(),
+                            exp.to_string(),
+                        )
+                    })
+            }
+
+            pub fn saturating_add(self, other: Self) -> Self {
+                $name(self.0.saturating_add(other.0))
+            }
+
+            pub fn saturating_sub(self, other: Self) -> Self {
+                $name(self.0.saturating_sub(other.0))
+            }
+
+            pub fn saturating_mul(self, other: Self) -> Self {
+                $name(self.0.saturating_mul(other.0))
+            }
+
+            pub fn saturating_pow(self, exp: u32) -> Self {
+                self.checked_pow(exp).unwrap_or(Self::MAX)
+            }
+
+            pub fn wrapping_add(self, other: Self) -> Self {
+                $name(self.0.overflowing_add(other.0).0)
+            }
+
+            pub fn wrapping_sub(self, other: Self) -> Self {
+                $name(self.0.overflowing_sub(other.0).0)
+            }
+
+            pub fn wrapping_mul(self, other: Self) -> Self {
+                $name(self.0.overflowing_mul(other.0).0)
+            }
+
+            pub fn wrapping_pow(self, exp: u32) -> Self {
+                $name(self.0.overflowing_pow($inner::from(exp)).0)
+            }
+        }
+
+        impl From<Uint128> for $name {
+            fn from(val: Uint128) -> Self {
+                $name($inner::from(val.u128()))
+            }
+        }
+
+        impl From<u128> for $name {
+            fn from(val: u128) -> Self {
+                $name($inner::from(val))
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(val: u64) -> Self {
+                $name($inner::from(val))
+            }
+        }
+
+        impl From<u32> for $name {
+            fn from(val: u32) -> Self {
+                $name($inner::from(val))
+            }
+        }
+
+        impl TryFrom<$name> for u128 {
+            type Error = StdError;
+
+            fn try_from(val: $name) -> Result<Self, Self::Error> {
+                u128::try_from(val.0)
+                    .map_err(|_| conversion_overflow(stringify!($name), "u128", val))
+            }
+        }
+
+        impl TryFrom<$name> for Uint128 {
+            type Error = StdError;
+
+            fn try_from(val: $name) -> Result<Self, Self::Error> {
+                u128::try_from(val.0)
+                    .map(Uint128)
+                    .map_err(|_| conversion_overflow(stringify!($name), "Uint128", val))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = StdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $inner::from_dec_str(s)
+                    .map($name)
+                    .map_err(|e| StdError::parse_err(stringify!($name), e))
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = StdError;
+
+            fn try_from(val: &str) -> Result<Self, Self::Error> {
+                $name::from_str(val)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(val: $name) -> Self {
+                val.to_string()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl ops::Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $name(
+                    self.0
+                        .checked_add(other.0)
+                        .expect("attempt to add with overflow"),
+                )
+            }
+        }
+
+        impl ops::AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $name(
+                    self.0
+                        .checked_sub(other.0)
+                        .expect("attempt to subtract with overflow"),
+                )
+            }
+        }
+
+        impl ops::SubAssign for $name {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl ops::Mul for $name {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self {
+                $name(
+                    self.0
+                        .checked_mul(other.0)
+                        .expect("attempt to multiply with overflow"),
+                )
+            }
+        }
+
+        impl ops::MulAssign for $name {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl ops::Div for $name {
+            type Output = Self;
+
+            fn div(self, other: Self) -> Self {
+                $name(
+                    self.0
+                        .checked_div(other.0)
+                        .expect("attempt to divide by zero"),
+                )
+            }
+        }
+
+        impl ops::DivAssign for $name {
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+
+        impl ops::Rem for $name {
+            type Output = Self;
+
+            fn rem(self, other: Self) -> Self {
+                $name(
+                    self.0
+                        .checked_rem(other.0)
+                        .expect("attempt to calculate the remainder with a divisor of zero"),
+                )
+            }
+        }
+
+        impl ops::RemAssign for $name {
+            fn rem_assign(&mut self, other: Self) {
+                *self = *self % other;
+            }
+        }
+
+        impl Sum<$name> for $name {
+            fn sum<I: Iterator<Item = $name>>(iter: I) -> Self {
+                iter.fold($name::zero(), ops::Add::add)
+            }
+        }
+
+        impl<'a> Sum<&'a $name> for $name {
+            fn sum<I: Iterator<Item = &'a $name>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        /// Serializes as a decimal string
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        /// Deserializes from a decimal string
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(FromStrVisitor::<$name>::new(stringify!($name)))
+            }
+        }
+    };
+}
+
+impl_big_uint!(Uint256, U256, 32);
+impl_big_uint!(Uint512, U512, 64);
+
+impl From<Uint256> for Uint512 {
+    fn from(val: Uint256) -> Self {
+        Uint512::from_le_bytes({
+            let mut bytes = [0u8; 64];
+            bytes[..32].copy_from_slice(&val.to_le_bytes());
+            bytes
+        })
+    }
+}
+
+impl TryFrom<Uint512> for Uint256 {
+    type Error = StdError;
+
+    fn try_from(val: Uint512) -> Result<Self, Self::Error> {
+        let bytes = val.to_le_bytes();
+        if bytes[32..].iter().any(|byte| *byte != 0) {
+            return Err(conversion_overflow("Uint512", "Uint256", val));
+        }
+        let mut low = [0u8; 32];
+        low.copy_from_slice(&bytes[..32]);
+        Ok(Uint256::from_le_bytes(low))
+    }
+}
+
+//*** Decimal256 ***/
+/// A fixed-point decimal value with 18 fractional digits backed by a Uint256, i.e.
+/// Decimal256::one() == 1.0. Use it for amounts that exceed `Decimal::MAX`.
+///
+/// The greatest possible value that can be represented is roughly 1.15 * 10^59 (which is (2^256 - 1) / 10^18)
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decimal256(Uint256);
+
+impl Decimal256 {
+    pub const MAX: Decimal256 = Decimal256(Uint256::MAX);
+
+    /// Create a 1.0 Decimal256
+    pub const fn one() -> Self {
+        // 10^18 fits into the lowest of the four u64 words
+        Decimal256(Uint256(U256([DECIMAL_FRACTIONAL as u64, 0, 0, 0])))
+    }
+
+    /// Create a 0.0 Decimal256
+    pub const fn zero() -> Self {
+        Decimal256(Uint256::zero())
+    }
+
+    /// Convert x% into Decimal256
+    pub fn percent(x: u64) -> Self {
+        Decimal256(Uint256::from(x) * Uint256::from(10_000_000_000_000_000u64))
+    }
+
+    /// Convert permille (x/1000) into Decimal256
+    pub fn permille(x: u64) -> Self {
+        Decimal256(Uint256::from(x) * Uint256::from(1_000_000_000_000_000u64))
+    }
+
+    /// Returns the ratio (nominator / denominator) as a Decimal256, rounded down.
+    /// Panics if the denominator is zero or the ratio does not fit into a Decimal256.
+    pub fn from_ratio<A: Into<Uint256>, B: Into<Uint256>>(nominator: A, denominator: B) -> Self {
+        match Decimal256::checked_from_ratio(nominator, denominator) {
+            Ok(value) => value,
+            Err(StdError::DivideByZero { .. }) => panic!("Denominator must not be zero"),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the ratio (nominator / denominator) as a Decimal256, rounded down
+    pub fn checked_from_ratio<A: Into<Uint256>, B: Into<Uint256>>(
+        nominator: A,
+        denominator: B,
+    ) -> StdResult<Self> {
+        let nominator: Uint256 = nominator.into();
+        let denominator: Uint256 = denominator.into();
+        if denominator.is_zero() {
+            return Err(StdError::divide_by_zero(nominator));
+        }
+        let atomics = Uint512::from(nominator) * Uint512::from(DECIMAL_FRACTIONAL)
+            / Uint512::from(denominator);
+        Uint256::try_from(atomics).map(Decimal256).map_err(|_| {
+            conversion_overflow(
+                "ratio",
+                "Decimal256",
+                format!("{}/{}", nominator, denominator),
+            )
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn checked_add(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Decimal256)
+            .map_err(|_| StdError::overflow(OverflowOperation::Add, self, other))
+    }
+
+    pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Decimal256)
+            .map_err(|_| StdError::overflow(OverflowOperation::Sub, self, other))
+    }
+
+    /// Multiplies two decimals, the result is rounded down to 18 decimal places
+    pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+        let product =
+            Uint512::from(self.0) * Uint512::from(other.0) / Uint512::from(DECIMAL_FRACTIONAL);
+        Uint256::try_from(product)
+            .map(Decimal256)
+            .map_err(|_| StdError::overflow(OverflowOperation::Mul, self, other))
+    }
+
+    /// Divides two decimals, the result is rounded down to 18 decimal places
+    pub fn checked_div(self, other: Self) -> StdResult<Self> {
+        if other.is_zero() {
+            return Err(StdError::divide_by_zero(self));
+        }
+        let quotient =
+            Uint512::from(self.0) * Uint512::from(DECIMAL_FRACTIONAL) / Uint512::from(other.0);
+        Uint256::try_from(quotient)
+            .map(Decimal256)
+            .map_err(|_| StdError::overflow(OverflowOperation::Div, self, other))
+    }
+
+    /// Raises the decimal to the given power by squaring, every step is rounded down
+    pub fn checked_pow(self, exp: u32) -> StdResult<Self> {
+        let overflow =
+            |_| StdError::overflow(OverflowOperation::Pow, self.to_string(), exp.to_string());
+
+        let mut base = self;
+        let mut exp_left = exp;
+        let mut result = Decimal256::one();
+        while exp_left > 0 {
+            if exp_left % 2 == 1 {
+                result = result.checked_mul(base).map_err(overflow)?;
+            }
+            exp_left /= 2;
+            if exp_left > 0 {
+                base = base.checked_mul(base).map_err(overflow)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl FromStr for Decimal256 {
+    type Err = StdError;
+
+    /// Converts the decimal string to a Decimal256, accepting the same inputs as Decimal
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (whole, fractional) = parse_decimal_str(input, |whole| Uint256::from_str(whole).ok())?;
+        let atomics = whole
+            .checked_mul(Uint256::from(DECIMAL_FRACTIONAL))
+            .and_then(|whole_as_atomics| whole_as_atomics.checked_add(Uint256::from(fractional)))
+            .map_err(|_| StdError::generic_err("Value too big"))?;
+        Ok(Decimal256(atomics))
+    }
+}
+
+impl fmt::Display for Decimal256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fractional_unit = Uint256::from(DECIMAL_FRACTIONAL);
+        let whole = self.0 / fractional_unit;
+        let fractional = u128::try_from(self.0 % fractional_unit).unwrap();
+        write_decimal(f, &whole, fractional)
+    }
+}
+
+impl ops::Add for Decimal256 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl ops::AddAssign for Decimal256 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for Decimal256 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl ops::SubAssign for Decimal256 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Mul for Decimal256 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl ops::MulAssign for Decimal256 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for Decimal256 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        match self.checked_div(other) {
+            Ok(quotient) => quotient,
+            Err(StdError::DivideByZero { .. }) => panic!("attempt to divide by zero"),
+            Err(_) => panic!("attempt to divide with overflow"),
+        }
+    }
+}
+
+impl ops::DivAssign for Decimal256 {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl Sum<Decimal256> for Decimal256 {
+    fn sum<I: Iterator<Item = Decimal256>>(iter: I) -> Self {
+        iter.fold(Decimal256::zero(), ops::Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Decimal256> for Decimal256 {
+    fn sum<I: Iterator<Item = &'a Decimal256>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Serializes as a decimal string
+impl Serialize for Decimal256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes from a decimal string
+impl<'de> Deserialize<'de> for Decimal256 {
+    fn deserialize<D>(deserializer: D) -> Result<Decimal256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FromStrVisitor::<Decimal256>::new("Decimal256"))
+    }
+}
+
+//*** Int128 ***/
+/// A 128 bit signed integer, serialized as a string in JSON like Uint128.
+///
+/// The operators panic on overflow and division by zero. Use the `checked_*` methods to get
+/// an `StdError` instead.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Int128(pub i128);
+
+impl Int128 {
+    pub const MAX: Int128 = Int128(i128::MAX);
+    pub const MIN: Int128 = Int128(i128::MIN);
+
+    pub const fn zero() -> Self {
+        Int128(0)
+    }
+
+    /// Returns a copy of the internal data
+    pub fn i128(&self) -> i128 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Add, self, other))
+    }
+
+    pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, self, other))
+    }
+
+    pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_mul(other.0)
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Mul, self, other))
+    }
+
+    /// Fails on a zero divisor, and on Int128::MIN / -1 which does not fit into an Int128
+    pub fn checked_div(self, other: Self) -> StdResult<Self> {
+        if other.is_zero() {
+            return Err(StdError::divide_by_zero(self));
+        }
+        self.0
+            .checked_div(other.0)
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Div, self, other))
+    }
+
+    pub fn checked_rem(self, other: Self) -> StdResult<Self> {
+        if other.is_zero() {
+            return Err(StdError::divide_by_zero(self));
+        }
+        self.0
+            .checked_rem(other.0)
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Div, self, other))
+    }
+
+    pub fn checked_pow(self, exp: u32) -> StdResult<Self> {
+        self.0.checked_pow(exp).map(Int128).ok_or_else(|| {
+            StdError::overflow(OverflowOperation::Pow, self.to_string(), exp.to_string())
+        })
+    }
+
+    /// Negation is a subtraction from zero, which overflows for Int128::MIN
+    pub fn checked_neg(self) -> StdResult<Self> {
+        self.0
+            .checked_neg()
+            .map(Int128)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, Int128::zero(), self))
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Int128(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Int128(self.0.saturating_sub(other.0))
+    }
+
+    pub fn saturating_mul(self, other: Self) -> Self {
+        Int128(self.0.saturating_mul(other.0))
+    }
+
+    pub fn saturating_pow(self, exp: u32) -> Self {
+        Int128(self.0.saturating_pow(exp))
+    }
+
+    pub fn wrapping_add(self, other: Self) -> Self {
+        Int128(self.0.wrapping_add(other.0))
+    }
+
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        Int128(self.0.wrapping_sub(other.0))
+    }
+
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        Int128(self.0.wrapping_mul(other.0))
+    }
+
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        Int128(self.0.wrapping_pow(exp))
+    }
+}
+
+impl From<i128> for Int128 {
+    fn from(val: i128) -> Self {
+        Int128(val)
+    }
+}
+
+impl From<i64> for Int128 {
+    fn from(val: i64) -> Self {
+        Int128(val.into())
+    }
+}
+
+impl From<u64> for Int128 {
+    fn from(val: u64) -> Self {
+        Int128(val.into())
+    }
+}
+
+impl From<Int128> for i128 {
+    fn from(val: Int128) -> Self {
+        val.0
+    }
+}
+
+impl FromStr for Int128 {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<i128>()
+            .map(Int128)
+            .map_err(|e| StdError::parse_err("Int128", e))
+    }
+}
+
+impl TryFrom<&str> for Int128 {
+    type Error = StdError;
+
+    fn try_from(val: &str) -> Result<Self, Self::Error> {
+        Int128::from_str(val)
+    }
+}
+
+impl From<Int128> for String {
+    fn from(val: Int128) -> Self {
+        val.to_string()
+    }
+}
+
+impl fmt::Display for Int128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ops::Add for Int128 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Int128(
+            self.0
+                .checked_add(other.0)
+                .expect("attempt to add with overflow"),
+        )
+    }
+}
+
+impl ops::AddAssign for Int128 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for Int128 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Int128(
+            self.0
+                .checked_sub(other.0)
+                .expect("attempt to subtract with overflow"),
+        )
+    }
+}
+
+impl ops::SubAssign for Int128 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Mul for Int128 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Int128(
+            self.0
+                .checked_mul(other.0)
+                .expect("attempt to multiply with overflow"),
+        )
+    }
+}
+
+impl ops::MulAssign for Int128 {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for Int128 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        Int128(
+            self.0
+                .checked_div(other.0)
+                .expect("attempt to divide with overflow"),
+        )
+    }
+}
+
+impl ops::DivAssign for Int128 {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl ops::Rem for Int128 {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("attempt to calculate the remainder with a divisor of zero");
+        }
+        Int128(
+            self.0
+                .checked_rem(other.0)
+                .expect("attempt to calculate the remainder with overflow"),
+        )
+    }
+}
+
+impl ops::RemAssign for Int128 {
+    fn rem_assign(&mut self, other: Self) {
+        *self = *self % other;
+    }
+}
+
+impl ops::Neg for Int128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Int128(
+            self.0
+                .checked_neg()
+                .expect("attempt to negate with overflow"),
+        )
+    }
+}
+
+impl Sum<Int128> for Int128 {
+    fn sum<I: Iterator<Item = Int128>>(iter: I) -> Self {
+        iter.fold(Int128::zero(), ops::Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Int128> for Int128 {
+    fn sum<I: Iterator<Item = &'a Int128>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Serializes as a decimal string
+impl Serialize for Int128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes from a decimal string
+impl<'de> Deserialize<'de> for Int128 {
+    fn deserialize<D>(deserializer: D) -> Result<Int128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FromStrVisitor::<Int128>::new("Int128"))
+    }
+}
+
+//*** SignedDecimal ***/
+/// A signed fixed-point decimal value with 18 fractional digits, i.e. SignedDecimal::one() == 1.0
+///
+/// The range is -170141183460469231731.687303715884105728 to 170141183460469231731.687303715884105727
+/// (which is i128::MIN / 10^18 to i128::MAX / 10^18)
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SignedDecimal(i128);
+
+/// Applies a sign to an unsigned magnitude, or None if the result does not fit into an i128
+fn signed_from_magnitude(negative: bool, magnitude: u128) -> Option<i128> {
+    if negative {
+        // the magnitude of i128::MIN is one more than i128::MAX
+        if magnitude <= i128::MAX as u128 + 1 {
+            Some((magnitude as i128).wrapping_neg())
+        } else {
+            None
+        }
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+impl SignedDecimal {
+    pub const MAX: SignedDecimal = SignedDecimal(i128::MAX);
+    pub const MIN: SignedDecimal = SignedDecimal(i128::MIN);
+
+    /// Create a 1.0 SignedDecimal
+    pub const fn one() -> Self {
+        SignedDecimal(DECIMAL_FRACTIONAL as i128)
+    }
+
+    /// Create a 0.0 SignedDecimal
+    pub const fn zero() -> Self {
+        SignedDecimal(0)
+    }
+
+    /// Convert x% into SignedDecimal
+    pub fn percent(x: i64) -> Self {
+        SignedDecimal((x as i128) * 10_000_000_000_000_000)
+    }
+
+    /// Convert permille (x/1000) into SignedDecimal
+    pub fn permille(x: i64) -> Self {
+        SignedDecimal((x as i128) * 1_000_000_000_000_000)
+    }
+
+    /// Returns the ratio (nominator / denominator) as a SignedDecimal, rounded towards zero.
+    /// Panics if the denominator is zero or the ratio does not fit into a SignedDecimal.
+    pub fn from_ratio<A: Into<i128>, B: Into<i128>>(nominator: A, denominator: B) -> Self {
+        match SignedDecimal::checked_from_ratio(nominator, denominator) {
+            Ok(value) => value,
+            Err(StdError::DivideByZero { .. }) => panic!("Denominator must not be zero"),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the ratio (nominator / denominator) as a SignedDecimal, rounded towards zero
+    pub fn checked_from_ratio<A: Into<i128>, B: Into<i128>>(
+        nominator: A,
+        denominator: B,
+    ) -> StdResult<Self> {
+        let nominator: i128 = nominator.into();
+        let denominator: i128 = denominator.into();
+        if denominator == 0 {
+            return Err(StdError::divide_by_zero(nominator));
+        }
+        let magnitude = Uint256::from(nominator.unsigned_abs()) * Uint256::from(DECIMAL_FRACTIONAL)
+            / Uint256::from(denominator.unsigned_abs());
+        u128::try_from(magnitude)
+            .ok()
+            .and_then(|magnitude| {
+                signed_from_magnitude((nominator < 0) != (denominator < 0), magnitude)
+            })
+            .map(SignedDecimal)
+            .ok_or_else(|| {
+                conversion_overflow(
+                    "ratio",
+                    "SignedDecimal",
+                    format!("{}/{}", nominator, denominator),
+                )
+            })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// Returns the absolute value, which does not fit into a SignedDecimal for SignedDecimal::MIN
+    pub fn checked_abs(self) -> StdResult<Self> {
+        if self.is_negative() {
+            self.checked_neg()
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Negation is a subtraction from zero, which overflows for SignedDecimal::MIN
+    pub fn checked_neg(self) -> StdResult<Self> {
+        self.0
+            .checked_neg()
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, SignedDecimal::zero(), self))
+    }
+
+    pub fn checked_add(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Add, self, other))
+    }
+
+    pub fn checked_sub(self, other: Self) -> StdResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Sub, self, other))
+    }
+
+    /// Multiplies two decimals, the result is rounded towards zero to 18 decimal places
+    pub fn checked_mul(self, other: Self) -> StdResult<Self> {
+        let magnitude = Uint256::from(self.0.unsigned_abs())
+            * Uint256::from(other.0.unsigned_abs())
+            / Uint256::from(DECIMAL_FRACTIONAL);
+        u128::try_from(magnitude)
+            .ok()
+            .and_then(|magnitude| {
+                signed_from_magnitude(self.is_negative() != other.is_negative(), magnitude)
+            })
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Mul, self, other))
+    }
+
+    /// Divides two decimals, the result is rounded towards zero to 18 decimal places
+    pub fn checked_div(self, other: Self) -> StdResult<Self> {
+        if other.is_zero() {
+            return Err(StdError::divide_by_zero(self));
+        }
+        let magnitude = Uint256::from(self.0.unsigned_abs()) * Uint256::from(DECIMAL_FRACTIONAL)
+            / Uint256::from(other.0.unsigned_abs());
+        u128::try_from(magnitude)
+            .ok()
+            .and_then(|magnitude| {
+                signed_from_magnitude(self.is_negative() != other.is_negative(), magnitude)
+            })
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::overflow(OverflowOperation::Div, self, other))
+    }
 }
 
-impl FromStr for Decimal {
+impl FromStr for SignedDecimal {
     type Err = StdError;
 
-    /// Converts the decimal string to a Decimal
-    /// Possible inputs: "1.23", "1", "000012", "1.123000000"
-    /// Disallowed: "", ".23"
-    ///
-    /// This never performs any kind of rounding.
-    /// More than 18 fractional digits, even zeros, result in an error.
+    /// Converts the decimal string to a SignedDecimal. It accepts the same inputs as Decimal,
+    /// optionally prefixed with a minus sign: "-1.23", "-1", "0.5"
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = input.split('.').collect();
-        match parts.len() {
-            1 => {
-                let whole = parts[0]
-                    .parse::<u128>()
-                    .map_err(|_| StdError::generic_err("Error parsing whole"))?;
-
-                let whole_as_atomics = whole
-                    .checked_mul(DECIMAL_FRACTIONAL)
-                    .ok_or_else(|| StdError::generic_err("Value too big"))?;
-                Ok(Decimal(whole_as_atomics))
-            }
-            2 => {
-                let whole = parts[0]
-                    .parse::<u128>()
-                    .map_err(|_| StdError::generic_err("Error parsing whole"))?;
-                let fractional = parts[1]
-                    .parse::<u128>()
-                    .map_err(|_| StdError::generic_err("Error parsing fractional"))?;
-                let exp = (18usize.checked_sub(parts[1].len())).ok_or_else(|| {
-                    StdError::generic_err("Cannot parse more than 18 fractional digits")
-                })?;
-                let fractional_factor = 10u128
-                    .checked_pow(exp.try_into().unwrap())
-                    .ok_or_else(|| StdError::generic_err("Cannot compute fractional factor"))?;
-
-                let whole_as_atomics = whole
-                    .checked_mul(DECIMAL_FRACTIONAL)
-                    .ok_or_else(|| StdError::generic_err("Value too big"))?;
-                let atomics = whole_as_atomics
-                    .checked_add(fractional * fractional_factor)
-                    .ok_or_else(|| StdError::generic_err("Value too big"))?;
-                Ok(Decimal(atomics))
-            }
-            _ => Err(StdError::generic_err("Unexpected number of dots")),
-        }
+        let (negative, unsigned) = match input.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, input),
+        };
+        let magnitude = Decimal::from_str(unsigned)?.0;
+        signed_from_magnitude(negative, magnitude)
+            .map(SignedDecimal)
+            .ok_or_else(|| StdError::generic_err("Value too big"))
     }
 }
 
-impl fmt::Display for Decimal {
+impl fmt::Display for SignedDecimal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let whole = (self.0) / DECIMAL_FRACTIONAL;
-        let fractional = (self.0) % DECIMAL_FRACTIONAL;
-
-        if fractional == 0 {
-            write!(f, "{}", whole)
-        } else {
-            let fractional_string = format!("{:018}", fractional);
-            f.write_str(&whole.to_string())?;
-            f.write_char('.')?;
-            f.write_str(fractional_string.trim_end_matches('0'))?;
-            Ok(())
+        if self.is_negative() {
+            f.write_char('-')?;
         }
+        let magnitude = self.0.unsigned_abs();
+        write_decimal(
+            f,
+            &(magnitude / DECIMAL_FRACTIONAL),
+            magnitude % DECIMAL_FRACTIONAL,
+        )
     }
 }
 
-impl ops::Add for Decimal {
+impl ops::Add for SignedDecimal {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        Decimal(self.0 + other.0)
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl ops::AddAssign for SignedDecimal {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for SignedDecimal {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl ops::SubAssign for SignedDecimal {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Mul for SignedDecimal {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl ops::MulAssign for SignedDecimal {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl ops::Div for SignedDecimal {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        match self.checked_div(other) {
+            Ok(quotient) => quotient,
+            Err(StdError::DivideByZero { .. }) => panic!("attempt to divide by zero"),
+            Err(_) => panic!("attempt to divide with overflow"),
+        }
+    }
+}
+
+impl ops::DivAssign for SignedDecimal {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl ops::Neg for SignedDecimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+impl Sum<SignedDecimal> for SignedDecimal {
+    fn sum<I: Iterator<Item = SignedDecimal>>(iter: I) -> Self {
+        iter.fold(SignedDecimal::zero(), ops::Add::add)
+    }
+}
+
+impl<'a> Sum<&'a SignedDecimal> for SignedDecimal {
+    fn sum<I: Iterator<Item = &'a SignedDecimal>>(iter: I) -> Self {
+        iter.copied().sum()
     }
 }
 
 /// Serializes as a decimal string
-impl Serialize for Decimal {
+impl Serialize for SignedDecimal {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
@@ -137,199 +1940,267 @@ impl Serialize for Decimal {
     }
 }
 
-/// Deserializes as a base64 string
-impl<'de> Deserialize<'de> for Decimal {
-    fn deserialize<D>(deserializer: D) -> Result<Decimal, D::Error>
+/// Deserializes from a decimal string
+impl<'de> Deserialize<'de> for SignedDecimal {
+    fn deserialize<D>(deserializer: D) -> Result<SignedDecimal, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(DecimalVisitor)
+        deserializer.deserialize_str(FromStrVisitor::<SignedDecimal>::new("SignedDecimal"))
     }
 }
 
-struct DecimalVisitor;
-
-impl<'de> de::Visitor<'de> for DecimalVisitor {
-    type Value = Decimal;
+//*** Conversions between the number types ***/
+impl TryFrom<Uint128> for Int128 {
+    type Error = StdError;
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("string-encoded decimal")
+    fn try_from(val: Uint128) -> Result<Self, Self::Error> {
+        i128::try_from(val.0)
+            .map(Int128)
+            .map_err(|_| conversion_overflow("Uint128", "Int128", val))
     }
+}
 
-    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-    where
-        E: de::Error,
-    {
-        match Decimal::from_str(v) {
-            Ok(d) => Ok(d),
-            Err(e) => Err(E::custom(format!("Error parsing decimal '{}': {}", v, e))),
-        }
+impl TryFrom<Int128> for Uint128 {
+    type Error = StdError;
+
+    fn try_from(val: Int128) -> Result<Self, Self::Error> {
+        u128::try_from(val.0)
+            .map(Uint128)
+            .map_err(|_| conversion_overflow("Int128", "Uint128", val))
     }
 }
 
-//*** Uint128 ***/
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Uint128(pub u128);
+impl TryFrom<Int128> for Uint256 {
+    type Error = StdError;
 
-impl Uint128 {
-    /// Creates a Uint128(0)
-    pub const fn zero() -> Self {
-        Uint128(0)
+    fn try_from(val: Int128) -> Result<Self, Self::Error> {
+        Uint128::try_from(val)
+            .map(Uint256::from)
+            .map_err(|_| conversion_overflow("Int128", "Uint256", val))
     }
+}
 
-    /// Returns a copy of the internal data
-    pub fn u128(&self) -> u128 {
-        self.0
+impl TryFrom<Uint256> for Int128 {
+    type Error = StdError;
+
+    fn try_from(val: Uint256) -> Result<Self, Self::Error> {
+        u128::try_from(val)
+            .ok()
+            .and_then(|val| i128::try_from(val).ok())
+            .map(Int128)
+            .ok_or_else(|| conversion_overflow("Uint256", "Int128", val))
     }
+}
 
-    pub fn is_zero(&self) -> bool {
-        self.0 == 0
+impl TryFrom<Int128> for Uint512 {
+    type Error = StdError;
+
+    fn try_from(val: Int128) -> Result<Self, Self::Error> {
+        Uint128::try_from(val)
+            .map(Uint512::from)
+            .map_err(|_| conversion_overflow("Int128", "Uint512", val))
     }
 }
 
-impl From<u128> for Uint128 {
-    fn from(val: u128) -> Self {
-        Uint128(val)
+impl TryFrom<Uint512> for Int128 {
+    type Error = StdError;
+
+    fn try_from(val: Uint512) -> Result<Self, Self::Error> {
+        u128::try_from(val)
+            .ok()
+            .and_then(|val| i128::try_from(val).ok())
+            .map(Int128)
+            .ok_or_else(|| conversion_overflow("Uint512", "Int128", val))
     }
 }
 
-impl From<u64> for Uint128 {
-    fn from(val: u64) -> Self {
-        Uint128(val.into())
+impl From<Decimal> for Decimal256 {
+    fn from(val: Decimal) -> Self {
+        Decimal256(Uint256::from(val.0))
     }
 }
 
-impl TryFrom<&str> for Uint128 {
+impl TryFrom<Decimal256> for Decimal {
     type Error = StdError;
 
-    fn try_from(val: &str) -> Result<Self, Self::Error> {
-        match val.parse::<u128>() {
-            Ok(u) => Ok(Uint128(u)),
-            Err(e) => Err(StdError::generic_err(format!("Parsing coin: {}", e))),
-        }
+    fn try_from(val: Decimal256) -> Result<Self, Self::Error> {
+        u128::try_from(val.0)
+            .map(Decimal)
+            .map_err(|_| conversion_overflow("Decimal256", "Decimal", val))
     }
 }
 
-impl Into<String> for Uint128 {
-    fn into(self) -> String {
-        self.0.to_string()
-    }
-}
+impl TryFrom<Decimal> for SignedDecimal {
+    type Error = StdError;
 
-impl Into<u128> for Uint128 {
-    fn into(self) -> u128 {
-        self.0
+    fn try_from(val: Decimal) -> Result<Self, Self::Error> {
+        i128::try_from(val.0)
+            .map(SignedDecimal)
+            .map_err(|_| conversion_overflow("Decimal", "SignedDecimal", val))
     }
 }
 
-impl fmt::Display for Uint128 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+impl TryFrom<SignedDecimal> for Decimal {
+    type Error = StdError;
+
+    fn try_from(val: SignedDecimal) -> Result<Self, Self::Error> {
+        u128::try_from(val.0)
+            .map(Decimal)
+            .map_err(|_| conversion_overflow("SignedDecimal", "Decimal", val))
     }
 }
 
-impl ops::Add for Uint128 {
-    type Output = Self;
+impl TryFrom<Decimal256> for SignedDecimal {
+    type Error = StdError;
 
-    fn add(self, other: Self) -> Self {
-        Uint128(self.u128() + other.u128())
+    fn try_from(val: Decimal256) -> Result<Self, Self::Error> {
+        u128::try_from(val.0)
+            .ok()
+            .and_then(|atomics| i128::try_from(atomics).ok())
+            .map(SignedDecimal)
+            .ok_or_else(|| conversion_overflow("Decimal256", "SignedDecimal", val))
     }
 }
 
-impl ops::AddAssign for Uint128 {
-    fn add_assign(&mut self, other: Self) {
-        self.0 += other.u128();
+impl TryFrom<SignedDecimal> for Decimal256 {
+    type Error = StdError;
+
+    fn try_from(val: SignedDecimal) -> Result<Self, Self::Error> {
+        u128::try_from(val.0)
+            .map(|atomics| Decimal256(Uint256::from(atomics)))
+            .map_err(|_| conversion_overflow("SignedDecimal", "Decimal256", val))
     }
 }
 
-impl ops::Sub for Uint128 {
-    type Output = StdResult<Self>;
+impl TryFrom<Int128> for SignedDecimal {
+    type Error = StdError;
 
-    fn sub(self, other: Self) -> StdResult<Self> {
-        let (min, sub) = (self.u128(), other.u128());
-        if sub > min {
-            Err(StdError::underflow(min, sub))
-        } else {
-            Ok(Uint128(min - sub))
-        }
+    fn try_from(val: Int128) -> Result<Self, Self::Error> {
+        val.0
+            .checked_mul(DECIMAL_FRACTIONAL as i128)
+            .map(SignedDecimal)
+            .ok_or_else(|| conversion_overflow("Int128", "SignedDecimal", val))
     }
 }
 
-/// Both d*u and u*d with d: Decimal and u: Uint128 returns an Uint128. There is no
-/// specific reason for this decision other than the initial use cases we have. If you
-/// need a Decimal result for the same calculation, use Decimal(d*u) or Decimal(u*d).
-impl ops::Mul<Decimal> for Uint128 {
-    type Output = Self;
+/// Fails if the value has a fractional part
+impl TryFrom<SignedDecimal> for Int128 {
+    type Error = StdError;
 
-    #[allow(clippy::suspicious_arithmetic_impl)]
-    fn mul(self, rhs: Decimal) -> Self::Output {
-        // 0*a and b*0 is always 0
-        if self.is_zero() || rhs.is_zero() {
-            return Uint128::zero();
+    fn try_from(val: SignedDecimal) -> Result<Self, Self::Error> {
+        let fractional = DECIMAL_FRACTIONAL as i128;
+        if val.0 % fractional != 0 {
+            return Err(conversion_overflow("SignedDecimal", "Int128", val));
         }
-        self.multiply_ratio(rhs.0, DECIMAL_FRACTIONAL)
+        Ok(Int128(val.0 / fractional))
     }
 }
 
-impl ops::Mul<Uint128> for Decimal {
-    type Output = Uint128;
+/// Implements the conversions between an unsigned integer and a decimal type, whose atomics
+/// are stored as $atomics. Decimals with a fractional part do not convert to an integer.
+macro_rules! impl_uint_decimal_conversions {
+    ($uint:ident, $decimal:ident, $atomics:ty) => {
+        impl TryFrom<$uint> for $decimal {
+            type Error = StdError;
+
+            fn try_from(val: $uint) -> Result<Self, Self::Error> {
+                Uint512::from(val)
+                    .checked_mul(Uint512::from(DECIMAL_FRACTIONAL))
+                    .ok()
+                    .and_then(|atomics| <$atomics>::try_from(atomics).ok())
+                    .map($decimal)
+                    .ok_or_else(|| {
+                        conversion_overflow(stringify!($uint), stringify!($decimal), val)
+                    })
+            }
+        }
 
-    fn mul(self, rhs: Uint128) -> Self::Output {
-        rhs * self
-    }
+        impl TryFrom<$decimal> for $uint {
+            type Error = StdError;
+
+            fn try_from(val: $decimal) -> Result<Self, Self::Error> {
+                let atomics = Uint512::from(val.0);
+                let fractional = Uint512::from(DECIMAL_FRACTIONAL);
+                if !(atomics % fractional).is_zero() {
+                    return Err(conversion_overflow(
+                        stringify!($decimal),
+                        stringify!($uint),
+                        val,
+                    ));
+                }
+                $uint::try_from(atomics / fractional).map_err(|_| {
+                    conversion_overflow(stringify!($decimal), stringify!($uint), val)
+                })
+            }
+        }
+    };
 }
 
-impl Uint128 {
-    /// returns self * nom / denom
-    pub fn multiply_ratio<A: Into<u128>, B: Into<u128>>(&self, nom: A, denom: B) -> Uint128 {
-        let nominator: u128 = nom.into();
-        let denominator: u128 = denom.into();
-        if denominator == 0 {
-            panic!("Denominator must not be zero");
+impl_uint_decimal_conversions!(Uint128, Decimal, u128);
+impl_uint_decimal_conversions!(Uint256, Decimal, u128);
+impl_uint_decimal_conversions!(Uint512, Decimal, u128);
+impl_uint_decimal_conversions!(Uint128, Decimal256, Uint256);
+impl_uint_decimal_conversions!(Uint256, Decimal256, Uint256);
+impl_uint_decimal_conversions!(Uint512, Decimal256, Uint256);
+
+/// Implements TryFrom<$from> for $to by converting through $via, which holds every value both
+/// types have in common. Errors are reported as a conversion from $from to $to.
+macro_rules! impl_try_from_via {
+    ($from:ident, $to:ident, $via:ident) => {
+        impl TryFrom<$from> for $to {
+            type Error = StdError;
+
+            fn try_from(val: $from) -> Result<Self, Self::Error> {
+                $via::try_from(val)
+                    .and_then($to::try_from)
+                    .map_err(|_| conversion_overflow(stringify!($from), stringify!($to), val))
+            }
         }
-        // TODO: minimize rounding that takes place (using gcd algorithm)
-        let val = self.u128() * nominator / denominator;
-        Uint128::from(val)
-    }
+    };
 }
 
-/// Serializes as a base64 string
-impl Serialize for Uint128 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: ser::Serializer,
-    {
-        serializer.serialize_str(&self.to_string())
-    }
+impl_try_from_via!(Int128, Decimal, Uint128);
+impl_try_from_via!(Decimal, Int128, Uint128);
+impl_try_from_via!(Int128, Decimal256, Uint128);
+impl_try_from_via!(Decimal256, Int128, Uint128);
+impl_try_from_via!(Uint128, SignedDecimal, Decimal);
+impl_try_from_via!(SignedDecimal, Uint128, Decimal);
+impl_try_from_via!(Uint256, SignedDecimal, Decimal);
+impl_try_from_via!(SignedDecimal, Uint256, Decimal);
+impl_try_from_via!(Uint512, SignedDecimal, Decimal);
+impl_try_from_via!(SignedDecimal, Uint512, Decimal);
+
+/// Deserializes any string-encoded number type through its FromStr implementation
+struct FromStrVisitor<T> {
+    name: &'static str,
+    target: PhantomData<T>,
 }
 
-/// Deserializes as a base64 string
-impl<'de> Deserialize<'de> for Uint128 {
-    fn deserialize<D>(deserializer: D) -> Result<Uint128, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer.deserialize_str(Uint128Visitor)
+impl<T> FromStrVisitor<T> {
+    fn new(name: &'static str) -> Self {
+        FromStrVisitor {
+            name,
+            target: PhantomData,
+        }
     }
 }
 
-struct Uint128Visitor;
-
-impl<'de> de::Visitor<'de> for Uint128Visitor {
-    type Value = Uint128;
+impl<'de, T> de::Visitor<'de> for FromStrVisitor<T>
+where
+    T: FromStr<Err = StdError>,
+{
+    type Value = T;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("string-encoded integer")
+        write!(formatter, "string-encoded {}", self.name)
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        match v.parse::<u128>() {
-            Ok(u) => Ok(Uint128(u)),
-            Err(e) => Err(E::custom(format!("invalid Uint128 '{}' - {}", v, e))),
-        }
+        T::from_str(v).map_err(|e| E::custom(format!("invalid {} '{}' - {}", self.name, v, e)))
     }
 }
 
@@ -455,6 +2326,11 @@ mod test {
             StdError::GenericErr { msg, .. } => assert_eq!(msg, "Error parsing whole"),
             e => panic!("Unexpected error: {:?}", e),
         }
+
+        match Decimal::from_str("+1").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected plus sign"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
     }
 
     #[test]
@@ -499,52 +2375,203 @@ mod test {
     }
 
     #[test]
-    fn decimal_from_str_errors_for_invalid_number_of_dots() {
-        match Decimal::from_str("1.2.3").unwrap_err() {
-            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected number of dots"),
-            e => panic!("Unexpected error: {:?}", e),
-        }
-
-        match Decimal::from_str("1.2.3.4").unwrap_err() {
-            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected number of dots"),
-            e => panic!("Unexpected error: {:?}", e),
-        }
+    fn decimal_from_str_errors_for_invalid_number_of_dots() {
+        match Decimal::from_str("1.2.3").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected number of dots"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        match Decimal::from_str("1.2.3.4").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected number of dots"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decimal_from_str_errors_for_more_than_max_value() {
+        // Integer
+        match Decimal::from_str("340282366920938463464").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        // Decimal
+        match Decimal::from_str("340282366920938463464.0").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match Decimal::from_str("340282366920938463463.374607431768211456").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decimal_is_zero_works() {
+        assert_eq!(Decimal::zero().is_zero(), true);
+        assert_eq!(Decimal::percent(0).is_zero(), true);
+        assert_eq!(Decimal::permille(0).is_zero(), true);
+
+        assert_eq!(Decimal::one().is_zero(), false);
+        assert_eq!(Decimal::percent(123).is_zero(), false);
+        assert_eq!(Decimal::permille(1234).is_zero(), false);
+    }
+
+    #[test]
+    fn decimal_add() {
+        let value = Decimal::one() + Decimal::percent(50); // 1.5
+        assert_eq!(value.0, DECIMAL_FRACTIONAL * 3 / 2);
+    }
+
+    #[test]
+    fn decimal_checked_from_ratio_works() {
+        assert_eq!(
+            Decimal::checked_from_ratio(3u128, 2u128),
+            Ok(Decimal::percent(150))
+        );
+        assert_eq!(
+            Decimal::checked_from_ratio(1u128, 0u128),
+            Err(StdError::divide_by_zero(1u128))
+        );
+        assert!(Decimal::checked_from_ratio(u128::MAX, 1u128).is_err());
+
+        // nominator * 10^18 exceeds u128, the ratio itself does not
+        let big = 100_000_000_000_000_000_000_000u128;
+        assert_eq!(Decimal::from_ratio(big, big), Decimal::one());
+    }
+
+    #[test]
+    fn decimal_arithmetic() {
+        let a = Decimal::percent(150);
+        let b = Decimal::percent(50);
+
+        assert_eq!(a - b, Decimal::one());
+        assert_eq!(a * b, Decimal::percent(75));
+        assert_eq!(a / b, Decimal::percent(300));
+
+        let mut c = a;
+        c += b;
+        c -= Decimal::percent(25);
+        c *= Decimal::percent(200);
+        c /= Decimal::percent(50);
+        assert_eq!(c, Decimal::percent(700));
+
+        let sum: Decimal = [a, b].iter().sum();
+        assert_eq!(sum, Decimal::percent(200));
+
+        // 1/3 is rounded down
+        assert_eq!(
+            Decimal::one() / Decimal::percent(300),
+            Decimal(333_333_333_333_333_333)
+        );
+    }
+
+    #[test]
+    fn decimal_checked_arithmetic() {
+        assert_eq!(
+            Decimal::MAX.checked_add(Decimal(1)),
+            Err(StdError::overflow(
+                OverflowOperation::Add,
+                Decimal::MAX,
+                Decimal(1)
+            ))
+        );
+        assert_eq!(
+            Decimal::zero().checked_sub(Decimal(1)),
+            Err(StdError::overflow(
+                OverflowOperation::Sub,
+                Decimal::zero(),
+                Decimal(1)
+            ))
+        );
+        assert_eq!(
+            Decimal::MAX.checked_mul(Decimal::percent(200)),
+            Err(StdError::overflow(
+                OverflowOperation::Mul,
+                Decimal::MAX,
+                Decimal::percent(200)
+            ))
+        );
+        assert_eq!(
+            Decimal::one().checked_div(Decimal::zero()),
+            Err(StdError::divide_by_zero(Decimal::one()))
+        );
+        assert_eq!(
+            Decimal::MAX.checked_div(Decimal::percent(50)),
+            Err(StdError::overflow(
+                OverflowOperation::Div,
+                Decimal::MAX,
+                Decimal::percent(50)
+            ))
+        );
+        // the full precision of the intermediate product is kept
+        assert_eq!(Decimal::MAX.checked_mul(Decimal::one()), Ok(Decimal::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn decimal_sub_underflow_panics() {
+        let _ = Decimal::zero() - Decimal::one();
     }
 
     #[test]
-    fn decimal_from_str_errors_for_more_than_max_value() {
-        // Integer
-        match Decimal::from_str("340282366920938463464").unwrap_err() {
-            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
-            e => panic!("Unexpected error: {:?}", e),
-        }
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn decimal_div_by_zero_panics() {
+        let _ = Decimal::one() / Decimal::zero();
+    }
 
-        // Decimal
-        match Decimal::from_str("340282366920938463464.0").unwrap_err() {
-            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
-            e => panic!("Unexpected error: {:?}", e),
-        }
-        match Decimal::from_str("340282366920938463463.374607431768211456").unwrap_err() {
-            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Value too big"),
-            e => panic!("Unexpected error: {:?}", e),
-        }
+    #[test]
+    fn decimal_pow_works() {
+        assert_eq!(Decimal::percent(200).pow(0), Decimal::one());
+        assert_eq!(Decimal::percent(200).pow(10), Decimal::percent(102400));
+        assert_eq!(Decimal::percent(50).pow(3), Decimal::permille(125));
+        assert!(Decimal::percent(200).checked_pow(128).is_err());
     }
 
     #[test]
-    fn decimal_is_zero_works() {
-        assert_eq!(Decimal::zero().is_zero(), true);
-        assert_eq!(Decimal::percent(0).is_zero(), true);
-        assert_eq!(Decimal::permille(0).is_zero(), true);
+    fn decimal_sqrt_works() {
+        assert_eq!(Decimal::percent(400).sqrt(), Decimal::percent(200));
+        assert_eq!(Decimal::percent(25).sqrt(), Decimal::percent(50));
+        assert_eq!(Decimal::zero().sqrt(), Decimal::zero());
+        assert_eq!(
+            Decimal::percent(200).sqrt(),
+            Decimal::from_str("1.414213562373095048").unwrap()
+        );
+        // does not overflow
+        Decimal::MAX.sqrt();
+    }
 
-        assert_eq!(Decimal::one().is_zero(), false);
-        assert_eq!(Decimal::percent(123).is_zero(), false);
-        assert_eq!(Decimal::permille(1234).is_zero(), false);
+    #[test]
+    fn decimal_inv_works() {
+        assert_eq!(Decimal::percent(400).inv(), Some(Decimal::percent(25)));
+        assert_eq!(
+            Decimal::percent(300).inv(),
+            Some(Decimal(333_333_333_333_333_333))
+        );
+        assert_eq!(Decimal::zero().inv(), None);
     }
 
     #[test]
-    fn decimal_add() {
-        let value = Decimal::one() + Decimal::percent(50); // 1.5
-        assert_eq!(value.0, DECIMAL_FRACTIONAL * 3 / 2);
+    fn decimal_rounding_works() {
+        let value = Decimal::from_str("12.3456").unwrap();
+        assert_eq!(value.floor(), Decimal::percent(1200));
+        assert_eq!(value.ceil(), Decimal::percent(1300));
+        assert_eq!(value.round(), Decimal::percent(1200));
+        assert_eq!(Decimal::percent(1250).round(), Decimal::percent(1300));
+
+        assert_eq!(value.floor_to(2), Decimal::percent(1234));
+        assert_eq!(value.ceil_to(2), Decimal::percent(1235));
+        assert_eq!(value.round_to(2), Decimal::percent(1235));
+        assert_eq!(value.round_to(3), Decimal::from_str("12.346").unwrap());
+        assert_eq!(value.round_to(18), value);
+        assert_eq!(value.round_to(30), value);
+
+        assert_eq!(value.to_uint_floor(), Uint128(12));
+        assert_eq!(value.to_uint_ceil(), Uint128(13));
+        assert_eq!(value.to_uint_round(), Uint128(12));
+        assert_eq!(Decimal::percent(1200).to_uint_ceil(), Uint128(12));
+
+        assert!(Decimal::MAX.checked_ceil_to(0).is_err());
     }
 
     #[test]
@@ -670,17 +2697,19 @@ mod test {
         c += b;
         assert_eq!(c, Uint128(323456));
 
-        // error result on underflow (- would produce negative result)
+        // error result on underflow (- would produce negative result), like checked_sub
         let underflow = a - b;
         match underflow {
             Ok(_) => panic!("should error"),
-            Err(StdError::Underflow {
-                minuend,
-                subtrahend,
+            Err(StdError::Overflow {
+                operation: OverflowOperation::Sub,
+                operand1,
+                operand2,
                 ..
-            }) => assert_eq!((minuend, subtrahend), (a.to_string(), b.to_string())),
-            _ => panic!("expected underflow error"),
+            }) => assert_eq!((operand1, operand2), (a.to_string(), b.to_string())),
+            _ => panic!("expected overflow error"),
         }
+        assert_eq!(a - b, a.checked_sub(b));
     }
 
     #[test]
@@ -691,6 +2720,141 @@ mod test {
         let _ = almost_max + Uint128(12);
     }
 
+    #[test]
+    fn uint128_operators() {
+        let a = Uint128(12345);
+        let b = Uint128(100);
+
+        assert_eq!(a * b, Uint128(1234500));
+        assert_eq!(a / b, Uint128(123));
+        assert_eq!(a % b, Uint128(45));
+
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, Uint128(1234500));
+        c /= Uint128(1000);
+        assert_eq!(c, Uint128(1234));
+        c %= Uint128(1000);
+        assert_eq!(c, Uint128(234));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn uint128_mul_overflow_panics() {
+        let _ = Uint128::MAX * Uint128(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn uint128_div_by_zero_panics() {
+        let _ = Uint128(1) / Uint128::zero();
+    }
+
+    #[test]
+    fn uint128_checked_math() {
+        assert_eq!(Uint128(1).checked_add(Uint128(2)), Ok(Uint128(3)));
+        assert_eq!(
+            Uint128::MAX.checked_add(Uint128(1)),
+            Err(StdError::overflow(
+                OverflowOperation::Add,
+                Uint128::MAX,
+                Uint128(1)
+            ))
+        );
+        assert_eq!(Uint128(3).checked_sub(Uint128(2)), Ok(Uint128(1)));
+        assert_eq!(
+            Uint128(1).checked_sub(Uint128(2)),
+            Err(StdError::overflow(
+                OverflowOperation::Sub,
+                Uint128(1),
+                Uint128(2)
+            ))
+        );
+        assert_eq!(Uint128(3).checked_mul(Uint128(2)), Ok(Uint128(6)));
+        assert_eq!(
+            Uint128::MAX.checked_mul(Uint128(2)),
+            Err(StdError::overflow(
+                OverflowOperation::Mul,
+                Uint128::MAX,
+                Uint128(2)
+            ))
+        );
+        assert_eq!(Uint128(7).checked_div(Uint128(2)), Ok(Uint128(3)));
+        assert_eq!(
+            Uint128(7).checked_div(Uint128(0)),
+            Err(StdError::divide_by_zero(Uint128(7)))
+        );
+        assert_eq!(Uint128(7).checked_rem(Uint128(2)), Ok(Uint128(1)));
+        assert_eq!(
+            Uint128(7).checked_rem(Uint128(0)),
+            Err(StdError::divide_by_zero(Uint128(7)))
+        );
+        assert_eq!(Uint128(2).checked_pow(10), Ok(Uint128(1024)));
+        assert_eq!(
+            Uint128(2).checked_pow(128),
+            Err(StdError::overflow(OverflowOperation::Pow, 2u128, 128u128))
+        );
+    }
+
+    #[test]
+    fn uint128_saturating_math() {
+        assert_eq!(Uint128::MAX.saturating_add(Uint128(1)), Uint128::MAX);
+        assert_eq!(Uint128(1).saturating_sub(Uint128(2)), Uint128::zero());
+        assert_eq!(Uint128::MAX.saturating_mul(Uint128(2)), Uint128::MAX);
+        assert_eq!(Uint128(7).saturating_div(Uint128(2)), Uint128(3));
+        assert_eq!(Uint128(7).saturating_rem(Uint128(2)), Uint128(1));
+        assert_eq!(Uint128(2).saturating_pow(128), Uint128::MAX);
+    }
+
+    #[test]
+    fn uint128_wrapping_math() {
+        assert_eq!(Uint128::MAX.wrapping_add(Uint128(1)), Uint128::zero());
+        assert_eq!(Uint128(1).wrapping_sub(Uint128(2)), Uint128::MAX);
+        assert_eq!(
+            Uint128::MAX.wrapping_mul(Uint128(2)),
+            Uint128(u128::MAX - 1)
+        );
+        assert_eq!(Uint128(7).wrapping_div(Uint128(2)), Uint128(3));
+        assert_eq!(Uint128(7).wrapping_rem(Uint128(2)), Uint128(1));
+        assert_eq!(Uint128(2).wrapping_pow(128), Uint128::zero());
+    }
+
+    #[test]
+    fn uint128_is_hashable() {
+        let set: std::collections::HashSet<Uint128> =
+            [Uint128(1), Uint128(2), Uint128(1)].iter().copied().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Uint128(2)));
+    }
+
+    #[test]
+    fn uint128_sum() {
+        let nums = vec![Uint128(17), Uint128(123), Uint128(540), Uint128(82)];
+        let expected = Uint128(762);
+
+        let sum_as_ref: Uint128 = nums.iter().sum();
+        assert_eq!(expected, sum_as_ref);
+
+        let sum_as_owned: Uint128 = nums.into_iter().sum();
+        assert_eq!(expected, sum_as_owned);
+    }
+
+    #[test]
+    fn uint128_conversions() {
+        let a = Uint128::from(42u32);
+        assert_eq!(u64::try_from(a).unwrap(), 42);
+        assert!(u64::try_from(Uint128::MAX).is_err());
+
+        let b: u128 = Uint128(7).into();
+        assert_eq!(b, 7);
+
+        assert_eq!(Uint128::from_str("1234").unwrap(), Uint128(1234));
+        match Uint128::from_str("-1").unwrap_err() {
+            StdError::ParseErr { target, .. } => assert_eq!(target, "Uint128"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     // in this test the Decimal is on the right
     fn uint128_decimal_multiply() {
@@ -732,6 +2896,29 @@ mod test {
         assert_eq!(base.multiply_ratio(100u128, 120u128), Uint128(416));
     }
 
+    #[test]
+    fn u128_multiply_ratio_does_not_overflow_when_result_fits() {
+        // 10^30 * 10^30 does not fit into u128, the result does
+        let base = Uint128(1_000_000_000_000_000_000_000_000_000_000);
+        assert_eq!(
+            base.multiply_ratio(
+                1_000_000_000_000_000_000_000_000_000_000u128,
+                1_000_000_000_000_000_000_000_000_000_000u128
+            ),
+            base
+        );
+        assert_eq!(
+            Uint128::MAX.multiply_ratio(3u128, 4u128),
+            Uint128(u128::MAX / 4 * 3 + 2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "multiplication overflow")]
+    fn u128_multiply_ratio_panics_if_result_does_not_fit() {
+        Uint128::MAX.multiply_ratio(2u128, 1u128);
+    }
+
     #[test]
     #[should_panic(expected = "Denominator must not be zero")]
     fn u128_multiply_ratio_panics_for_zero_denominator() {
@@ -756,4 +2943,453 @@ mod test {
         let right = Uint128(0);
         assert_eq!(left * right, Uint128(0));
     }
+
+    #[test]
+    fn uint256_json() {
+        let orig = Uint256::from(u128::MAX) * Uint256::from(1000u32);
+        let serialized = to_vec(&orig).unwrap();
+        assert_eq!(
+            serialized.as_slice(),
+            b"\"340282366920938463463374607431768211455000\""
+        );
+        let parsed: Uint256 = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, orig);
+
+        let err = from_slice::<Uint256>(br#""1.5""#).unwrap_err();
+        assert!(err.to_string().contains("invalid Uint256 '1.5'"));
+    }
+
+    #[test]
+    fn uint256_math() {
+        let a = Uint256::from(12345u32);
+        let b = Uint256::from(100u32);
+
+        assert_eq!(a + b, Uint256::from(12445u32));
+        assert_eq!(a - b, Uint256::from(12245u32));
+        assert_eq!(a * b, Uint256::from(1234500u32));
+        assert_eq!(a / b, Uint256::from(123u32));
+        assert_eq!(a % b, Uint256::from(45u32));
+        assert!(a > b);
+
+        let sum: Uint256 = [a, b].iter().sum();
+        assert_eq!(sum, Uint256::from(12445u32));
+    }
+
+    #[test]
+    fn uint256_checked_math() {
+        assert_eq!(
+            Uint256::MAX.checked_add(Uint256::from(1u32)),
+            Err(StdError::overflow(
+                OverflowOperation::Add,
+                Uint256::MAX,
+                Uint256::from(1u32)
+            ))
+        );
+        assert_eq!(
+            Uint256::zero().checked_sub(Uint256::from(1u32)),
+            Err(StdError::overflow(
+                OverflowOperation::Sub,
+                Uint256::zero(),
+                Uint256::from(1u32)
+            ))
+        );
+        assert_eq!(
+            Uint256::from(7u32).checked_div(Uint256::zero()),
+            Err(StdError::divide_by_zero(Uint256::from(7u32)))
+        );
+        assert_eq!(
+            Uint256::from(2u32).checked_pow(255),
+            Ok(Uint256::from_be_bytes({
+                let mut bytes = [0u8; 32];
+                bytes[0] = 0x80;
+                bytes
+            }))
+        );
+        assert!(Uint256::from(2u32).checked_pow(256).is_err());
+        assert_eq!(Uint256::from(2u32).saturating_pow(256), Uint256::MAX);
+        assert_eq!(
+            Uint256::MAX.wrapping_add(Uint256::from(1u32)),
+            Uint256::zero()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn uint256_sub_underflow_panics() {
+        let _ = Uint256::zero() - Uint256::from(1u32);
+    }
+
+    #[test]
+    fn uint256_conversions() {
+        let a = Uint256::from(Uint128(42));
+        assert_eq!(Uint128::try_from(a).unwrap(), Uint128(42));
+        assert_eq!(u128::try_from(a).unwrap(), 42);
+        assert!(Uint128::try_from(Uint256::MAX).is_err());
+
+        assert_eq!(Uint256::from_le_bytes(a.to_le_bytes()), a);
+        assert_eq!(Uint256::from_be_bytes(a.to_be_bytes()), a);
+        assert_eq!(Uint256::from_str("42").unwrap(), a);
+        let s: String = a.into();
+        assert_eq!(s, "42");
+
+        let wide = Uint512::from(Uint256::MAX);
+        assert_eq!(Uint256::try_from(wide).unwrap(), Uint256::MAX);
+        assert!(Uint256::try_from(wide + Uint512::from(1u32)).is_err());
+    }
+
+    #[test]
+    fn uint512_math() {
+        let max = Uint512::from(Uint256::MAX);
+        let square = max * max;
+        assert_eq!(square / max, max);
+        assert!(square.checked_mul(max).is_err());
+
+        let orig = Uint512::from(12345u32);
+        let parsed: Uint512 = from_slice(&to_vec(&orig).unwrap()).unwrap();
+        assert_eq!(parsed, orig);
+    }
+
+    #[test]
+    fn decimal256_from_str_and_display() {
+        let value = Decimal256::from_str("123456789012345678901234567890.5").unwrap();
+        assert_eq!(value.to_string(), "123456789012345678901234567890.5");
+        assert_eq!(
+            Decimal256::from_str("1.25").unwrap(),
+            Decimal256::percent(125)
+        );
+        assert_eq!(
+            Decimal256::from_str("0.125").unwrap(),
+            Decimal256::permille(125)
+        );
+        assert_eq!(Decimal256::one().to_string(), "1");
+        match Decimal256::from_str("1.2.3").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Unexpected number of dots"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decimal256_serde() {
+        let value = Decimal::from_str("87.65").unwrap();
+        let serialized = to_vec(&Decimal256::from(value)).unwrap();
+        // same format as Decimal
+        assert_eq!(serialized, to_vec(&value).unwrap());
+        let parsed: Decimal256 = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, Decimal256::percent(8765));
+
+        let err = from_slice::<Decimal256>(br#""1.2.3""#).unwrap_err();
+        assert!(err.to_string().contains("invalid Decimal256 '1.2.3'"));
+    }
+
+    #[test]
+    fn decimal256_arithmetic() {
+        let a = Decimal256::percent(150);
+        let b = Decimal256::percent(50);
+
+        assert_eq!(a + b, Decimal256::percent(200));
+        assert_eq!(a - b, Decimal256::one());
+        assert_eq!(a * b, Decimal256::percent(75));
+        assert_eq!(a / b, Decimal256::percent(300));
+        assert_eq!(b.checked_pow(2), Ok(Decimal256::percent(25)));
+
+        // far beyond Decimal::MAX
+        let big = Decimal256::from_ratio(Uint256::from(u128::MAX), Uint256::from(1u32));
+        assert_eq!(
+            big * Decimal256::percent(200) / big,
+            Decimal256::percent(200)
+        );
+
+        assert!(Decimal256::MAX.checked_add(Decimal256::one()).is_err());
+        assert!(Decimal256::zero().checked_sub(Decimal256::one()).is_err());
+        assert!(Decimal256::MAX
+            .checked_mul(Decimal256::percent(200))
+            .is_err());
+        assert_eq!(
+            Decimal256::one().checked_div(Decimal256::zero()),
+            Err(StdError::divide_by_zero(Decimal256::one()))
+        );
+    }
+
+    #[test]
+    fn int128_math() {
+        let a = Int128(-12345);
+        let b = Int128(100);
+
+        assert_eq!(a + b, Int128(-12245));
+        assert_eq!(a - b, Int128(-12445));
+        assert_eq!(a * b, Int128(-1234500));
+        assert_eq!(a / b, Int128(-123));
+        assert_eq!(a % b, Int128(-45));
+        assert_eq!(-a, Int128(12345));
+        assert!(a < b);
+        assert!(a.is_negative());
+
+        let sum: Int128 = [a, b].iter().sum();
+        assert_eq!(sum, Int128(-12245));
+
+        assert_eq!(
+            Int128::MAX.checked_add(Int128(1)),
+            Err(StdError::overflow(
+                OverflowOperation::Add,
+                Int128::MAX,
+                Int128(1)
+            ))
+        );
+        assert_eq!(
+            Int128::MIN.checked_div(Int128(-1)),
+            Err(StdError::overflow(
+                OverflowOperation::Div,
+                Int128::MIN,
+                Int128(-1)
+            ))
+        );
+        assert_eq!(
+            a.checked_rem(Int128::zero()),
+            Err(StdError::divide_by_zero(a))
+        );
+        assert!(Int128::MIN.checked_neg().is_err());
+        assert_eq!(Int128::MIN.saturating_sub(Int128(1)), Int128::MIN);
+        assert_eq!(Int128::MAX.wrapping_add(Int128(1)), Int128::MIN);
+        assert_eq!(Int128(-2).checked_pow(3), Ok(Int128(-8)));
+    }
+
+    #[test]
+    fn int128_json() {
+        let orig = Int128(-1234567890987654321);
+        let serialized = to_vec(&orig).unwrap();
+        assert_eq!(serialized.as_slice(), b"\"-1234567890987654321\"");
+        let parsed: Int128 = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, orig);
+    }
+
+    #[test]
+    fn signed_decimal_from_str_and_display() {
+        assert_eq!(
+            SignedDecimal::from_str("-1.5").unwrap(),
+            SignedDecimal::percent(-150)
+        );
+        assert_eq!(
+            SignedDecimal::from_str("0.125").unwrap(),
+            SignedDecimal::permille(125)
+        );
+        assert_eq!(SignedDecimal::percent(-150).to_string(), "-1.5");
+        assert_eq!(SignedDecimal::percent(-1).to_string(), "-0.01");
+        assert_eq!(SignedDecimal::zero().to_string(), "0");
+        assert_eq!(
+            SignedDecimal::from_str(&SignedDecimal::MIN.to_string()).unwrap(),
+            SignedDecimal::MIN
+        );
+        assert!(SignedDecimal::from_str("170141183460469231732").is_err());
+        assert!(SignedDecimal::from_str("--1").is_err());
+        // like Decimal, no plus sign is accepted
+        assert!(SignedDecimal::from_str("+1").is_err());
+        assert!(SignedDecimal::from_str("-+1").is_err());
+        assert!(SignedDecimal::from_str("1.+5").is_err());
+    }
+
+    #[test]
+    fn signed_decimal_serde() {
+        let serialized = to_vec(&SignedDecimal::percent(-8765)).unwrap();
+        assert_eq!(serialized, br#""-87.65""#);
+        let parsed: SignedDecimal = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, SignedDecimal::percent(-8765));
+
+        // positive values are compatible with Decimal
+        let parsed: SignedDecimal = from_slice(&to_vec(&Decimal::percent(8)).unwrap()).unwrap();
+        assert_eq!(parsed, SignedDecimal::percent(8));
+    }
+
+    #[test]
+    fn signed_decimal_arithmetic() {
+        let a = SignedDecimal::percent(-150);
+        let b = SignedDecimal::percent(50);
+
+        assert_eq!(a + b, SignedDecimal::percent(-100));
+        assert_eq!(b - a, SignedDecimal::percent(200));
+        assert_eq!(a * b, SignedDecimal::percent(-75));
+        assert_eq!(a * a, SignedDecimal::percent(225));
+        assert_eq!(a / b, SignedDecimal::percent(-300));
+        assert_eq!(-a, SignedDecimal::percent(150));
+        assert_eq!(a.checked_abs(), Ok(SignedDecimal::percent(150)));
+        assert!(a < b);
+
+        // rounded towards zero
+        assert_eq!(
+            SignedDecimal::from_ratio(-1i128, 3i128),
+            SignedDecimal(-333_333_333_333_333_333)
+        );
+
+        assert!(SignedDecimal::MAX
+            .checked_add(SignedDecimal::one())
+            .is_err());
+        assert!(SignedDecimal::MIN
+            .checked_sub(SignedDecimal::one())
+            .is_err());
+        assert!(SignedDecimal::MIN.checked_abs().is_err());
+        assert!(SignedDecimal::MIN
+            .checked_mul(SignedDecimal::percent(200))
+            .is_err());
+        assert_eq!(
+            SignedDecimal::one().checked_div(SignedDecimal::zero()),
+            Err(StdError::divide_by_zero(SignedDecimal::one()))
+        );
+    }
+
+    #[test]
+    fn numeric_conversions() {
+        assert_eq!(Int128::try_from(Uint128(7)), Ok(Int128(7)));
+        assert!(Int128::try_from(Uint128::MAX).is_err());
+        assert_eq!(Uint128::try_from(Int128(7)), Ok(Uint128(7)));
+        assert!(Uint128::try_from(Int128(-7)).is_err());
+        assert_eq!(Uint256::try_from(Int128(7)), Ok(Uint256::from(7u32)));
+        assert!(Uint256::try_from(Int128(-7)).is_err());
+        assert_eq!(Int128::try_from(Uint256::from(7u32)), Ok(Int128(7)));
+        assert!(Int128::try_from(Uint256::MAX).is_err());
+        assert_eq!(Uint512::try_from(Int128(7)), Ok(Uint512::from(7u32)));
+        assert_eq!(Int128::try_from(Uint512::from(7u32)), Ok(Int128(7)));
+
+        let decimal = Decimal::percent(125);
+        assert_eq!(Decimal256::from(decimal), Decimal256::percent(125));
+        assert_eq!(Decimal::try_from(Decimal256::percent(125)), Ok(decimal));
+        assert!(Decimal::try_from(Decimal256::MAX).is_err());
+        assert_eq!(
+            SignedDecimal::try_from(decimal),
+            Ok(SignedDecimal::percent(125))
+        );
+        assert!(SignedDecimal::try_from(Decimal::MAX).is_err());
+        assert_eq!(Decimal::try_from(SignedDecimal::percent(125)), Ok(decimal));
+        assert!(Decimal::try_from(SignedDecimal::percent(-125)).is_err());
+        assert_eq!(
+            SignedDecimal::try_from(Decimal256::percent(125)),
+            Ok(SignedDecimal::percent(125))
+        );
+        assert!(SignedDecimal::try_from(Decimal256::MAX).is_err());
+        assert_eq!(
+            Decimal256::try_from(SignedDecimal::percent(125)),
+            Ok(Decimal256::percent(125))
+        );
+        assert!(Decimal256::try_from(SignedDecimal::percent(-125)).is_err());
+    }
+
+    #[test]
+    fn integer_decimal_conversions() {
+        assert_eq!(
+            SignedDecimal::try_from(Int128(-3)),
+            Ok(SignedDecimal::percent(-300))
+        );
+        assert!(SignedDecimal::try_from(Int128::MAX).is_err());
+        assert!(SignedDecimal::try_from(Int128::MIN).is_err());
+        assert_eq!(
+            Int128::try_from(SignedDecimal::percent(-300)),
+            Ok(Int128(-3))
+        );
+        assert!(Int128::try_from(SignedDecimal::percent(-150)).is_err());
+
+        assert_eq!(Decimal::try_from(Uint128(3)), Ok(Decimal::percent(300)));
+        assert!(Decimal::try_from(Uint128::MAX).is_err());
+        assert_eq!(Uint128::try_from(Decimal::percent(300)), Ok(Uint128(3)));
+        assert!(Uint128::try_from(Decimal::percent(150)).is_err());
+        assert_eq!(
+            Decimal::try_from(Uint256::from(3u32)),
+            Ok(Decimal::percent(300))
+        );
+        assert!(Decimal::try_from(Uint256::MAX).is_err());
+        assert_eq!(
+            Uint512::try_from(Decimal::percent(300)),
+            Ok(Uint512::from(3u32))
+        );
+        assert!(Decimal::try_from(Uint512::MAX).is_err());
+
+        assert_eq!(
+            Decimal256::try_from(Uint256::from(3u32)),
+            Ok(Decimal256::percent(300))
+        );
+        assert!(Decimal256::try_from(Uint256::MAX).is_err());
+        assert_eq!(
+            Uint256::try_from(Decimal256::percent(300)),
+            Ok(Uint256::from(3u32))
+        );
+        assert!(Uint256::try_from(Decimal256::percent(150)).is_err());
+        assert_eq!(
+            Decimal256::try_from(Uint128::MAX),
+            Ok(Decimal256::from_ratio(Uint128::MAX, 1u32))
+        );
+        assert_eq!(
+            Uint128::try_from(Decimal256::from_ratio(Uint128::MAX, 1u32)),
+            Ok(Uint128::MAX)
+        );
+        let too_big = Uint256::from(u128::MAX) + Uint256::from(1u32);
+        assert!(Uint128::try_from(Decimal256::from_ratio(too_big, 1u32)).is_err());
+        assert!(Decimal256::try_from(Uint512::MAX).is_err());
+        assert_eq!(Decimal256::one(), Decimal256::percent(100));
+    }
+
+    #[test]
+    fn signed_unsigned_decimal_conversions() {
+        assert_eq!(Decimal::try_from(Int128(3)), Ok(Decimal::percent(300)));
+        assert!(Decimal::try_from(Int128(-3)).is_err());
+        assert!(Decimal::try_from(Int128::MAX).is_err());
+        assert_eq!(Int128::try_from(Decimal::percent(300)), Ok(Int128(3)));
+        assert!(Int128::try_from(Decimal::percent(150)).is_err());
+        assert_eq!(
+            Int128::try_from(Decimal::MAX.floor()),
+            Ok(Int128((u128::MAX / DECIMAL_FRACTIONAL) as i128))
+        );
+
+        assert_eq!(
+            Decimal256::try_from(Int128(3)),
+            Ok(Decimal256::percent(300))
+        );
+        assert!(Decimal256::try_from(Int128(-3)).is_err());
+        assert_eq!(
+            Decimal256::try_from(Int128::MAX),
+            Ok(Decimal256::from_ratio(i128::MAX as u128, 1u32))
+        );
+        assert_eq!(Int128::try_from(Decimal256::percent(300)), Ok(Int128(3)));
+        assert!(Int128::try_from(Decimal256::percent(150)).is_err());
+        let too_big = Uint256::from(i128::MAX as u128) + Uint256::from(1u32);
+        assert!(Int128::try_from(Decimal256::from_ratio(too_big, 1u32)).is_err());
+
+        assert_eq!(
+            SignedDecimal::try_from(Uint128(3)),
+            Ok(SignedDecimal::percent(300))
+        );
+        assert!(SignedDecimal::try_from(Uint128::MAX).is_err());
+        assert_eq!(
+            Uint128::try_from(SignedDecimal::percent(300)),
+            Ok(Uint128(3))
+        );
+        assert!(Uint128::try_from(SignedDecimal::percent(-300)).is_err());
+        assert!(Uint128::try_from(SignedDecimal::percent(150)).is_err());
+
+        assert_eq!(
+            SignedDecimal::try_from(Uint256::from(3u32)),
+            Ok(SignedDecimal::percent(300))
+        );
+        assert!(SignedDecimal::try_from(Uint256::MAX).is_err());
+        assert_eq!(
+            Uint256::try_from(SignedDecimal::percent(300)),
+            Ok(Uint256::from(3u32))
+        );
+        assert!(Uint256::try_from(SignedDecimal::percent(-300)).is_err());
+        assert!(Uint256::try_from(SignedDecimal::percent(150)).is_err());
+
+        assert_eq!(
+            SignedDecimal::try_from(Uint512::from(3u32)),
+            Ok(SignedDecimal::percent(300))
+        );
+        assert!(SignedDecimal::try_from(Uint512::MAX).is_err());
+        assert_eq!(
+            Uint512::try_from(SignedDecimal::percent(300)),
+            Ok(Uint512::from(3u32))
+        );
+        assert!(Uint512::try_from(SignedDecimal::percent(-300)).is_err());
+        assert!(Uint512::try_from(SignedDecimal::percent(150)).is_err());
+
+        // errors name the requested conversion, not the intermediate type
+        assert_eq!(
+            SignedDecimal::try_from(Uint128::MAX),
+            Err(conversion_overflow("Uint128", "SignedDecimal", Uint128::MAX))
+        );
+    }
 }
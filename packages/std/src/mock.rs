@@ -1,16 +1,18 @@
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 
 use crate::addresses::{CanonicalAddr, HumanAddr};
-use crate::coins::Coin;
+use crate::bech32::Bech32Api;
+use crate::coins::{Coin, Coins};
 use crate::encoding::Binary;
 use crate::errors::{StdError, StdResult, SystemError, SystemResult};
 use crate::query::{
     AllBalanceResponse, AllDelegationsResponse, BalanceResponse, BankQuery, BondedDenomResponse,
-    DelegationResponse, DistQuery, FullDelegation, GovQuery, MintQuery, QueryRequest, StakingQuery,
-    Validator, ValidatorsResponse, WasmQuery,
+    BondedRatioResponse, Delegation, DelegationResponse, DistQuery, FullDelegation, GovQuery,
+    InflationResponse, MintQuery, Proposal, ProposalsResponse, QueryRequest, StakingQuery,
+    Validator, ValidatorRewards, ValidatorsResponse, WasmQuery,
 };
-use crate::serde::{from_slice, to_binary};
+use crate::serde::{from_slice, to_binary, to_vec};
 use crate::storage::MemoryStorage;
 use crate::traits::{Api, Extern, Querier, QuerierResult};
 use crate::types::{BlockInfo, ContractInfo, Empty, Env, MessageInfo};
@@ -45,6 +47,110 @@ pub fn mock_dependencies_with_balances(
     }
 }
 
+/// Builds the dependencies of a test, for more setups than mock_dependencies covers:
+///
+/// ```ignore
+/// let deps = MockDependenciesBuilder::new()
+///     .contract_balance(&coins(100, "uscrt"))
+///     .balance("alice", &coins(5, "uscrt"))
+///     .build();
+/// ```
+pub struct MockDependenciesBuilder<C: DeserializeOwned = Empty> {
+    api: MockApi,
+    balances: Vec<(HumanAddr, Vec<Coin>)>,
+    staking: StakingQuerier,
+    custom_handler: Option<MockCustomHandler<C>>,
+}
+
+impl MockDependenciesBuilder {
+    /// Starts with canonical addresses of 20 bytes and no balances
+    pub fn new() -> Self {
+        MockDependenciesBuilder {
+            api: MockApi::new(20),
+            balances: vec![],
+            staking: StakingQuerier::default(),
+            custom_handler: None,
+        }
+    }
+}
+
+impl Default for MockDependenciesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: DeserializeOwned> MockDependenciesBuilder<C> {
+    pub fn canonical_length(mut self, canonical_length: usize) -> Self {
+        self.api = MockApi::new(canonical_length);
+        self
+    }
+
+    /// Converts addresses with bech32 and the given prefix, see MockApi::new_bech32
+    pub fn bech32(mut self, prefix: &'static str) -> Self {
+        self.api = MockApi::new_bech32(prefix);
+        self
+    }
+
+    /// Sets the balance of the given address, replacing a previous one
+    pub fn balance<U: Into<HumanAddr>>(mut self, addr: U, balance: &[Coin]) -> Self {
+        let addr = addr.into();
+        self.balances.retain(|(existing, _)| existing != &addr);
+        self.balances.push((addr, balance.to_vec()));
+        self
+    }
+
+    /// Sets the balance of MOCK_CONTRACT_ADDR
+    pub fn contract_balance(self, balance: &[Coin]) -> Self {
+        self.balance(MOCK_CONTRACT_ADDR, balance)
+    }
+
+    #[cfg(feature = "staking")]
+    pub fn staking(
+        mut self,
+        denom: &str,
+        validators: &[Validator],
+        delegations: &[FullDelegation],
+    ) -> Self {
+        self.staking = StakingQuerier::new(denom, validators, delegations);
+        self
+    }
+
+    /// Handles the custom queries of type CQ, see MockQuerier::with_custom_handler
+    pub fn custom_handler<CQ: DeserializeOwned, CH>(
+        self,
+        handler: CH,
+    ) -> MockDependenciesBuilder<CQ>
+    where
+        CH: Fn(&CQ) -> MockQuerierCustomHandlerResult + 'static,
+    {
+        MockDependenciesBuilder {
+            api: self.api,
+            balances: self.balances,
+            staking: self.staking,
+            custom_handler: Some(Box::from(handler)),
+        }
+    }
+
+    pub fn build(self) -> Extern<MockStorage, MockApi, MockQuerier<C>> {
+        let balances: Vec<(&HumanAddr, &[Coin])> = self
+            .balances
+            .iter()
+            .map(|(addr, balance)| (addr, balance.as_slice()))
+            .collect();
+        let mut querier = MockQuerier::new(&balances);
+        querier.staking = self.staking;
+        if let Some(handler) = self.custom_handler {
+            querier.custom_handler = handler;
+        }
+        Extern {
+            storage: MockStorage::default(),
+            api: self.api,
+            querier,
+        }
+    }
+}
+
 // Use MemoryStorage implementation (which is valid in non-testcode)
 // We can later make simplifications here if needed
 pub type MockStorage = MemoryStorage;
@@ -52,14 +158,27 @@ pub type MockStorage = MemoryStorage;
 // MockPrecompiles zero pads all human addresses to make them fit the canonical_length
 // it trims off zeros for the reverse operation.
 // not really smart, but allows us to see a difference (and consistent length for canonical adddresses)
+// Use MockApi::new_bech32 to convert realistic addresses the same way the host does.
 #[derive(Copy, Clone)]
 pub struct MockApi {
     canonical_length: usize,
+    bech32: Option<Bech32Api>,
 }
 
 impl MockApi {
     pub fn new(canonical_length: usize) -> Self {
-        MockApi { canonical_length }
+        MockApi {
+            canonical_length,
+            bech32: None,
+        }
+    }
+
+    /// Creates a MockApi converting bech32 addresses with the given prefix, see `Bech32Api`
+    pub fn new_bech32(prefix: &'static str) -> Self {
+        MockApi {
+            canonical_length: 20,
+            bech32: Some(Bech32Api::new(prefix)),
+        }
     }
 }
 
@@ -71,6 +190,10 @@ impl Default for MockApi {
 
 impl Api for MockApi {
     fn canonical_address(&self, human: &HumanAddr) -> StdResult<CanonicalAddr> {
+        if let Some(bech32) = self.bech32 {
+            return bech32.canonical_address(human);
+        }
+
         // Dummy input validation. This is more sophisticated for formats like bech32, where format and checksum are validated.
         if human.len() < 3 {
             return Err(StdError::generic_err(
@@ -92,6 +215,10 @@ impl Api for MockApi {
     }
 
     fn human_address(&self, canonical: &CanonicalAddr) -> StdResult<HumanAddr> {
+        if let Some(bech32) = self.bech32 {
+            return bech32.human_address(canonical);
+        }
+
         if canonical.len() != self.canonical_length {
             return Err(StdError::generic_err(
                 "Invalid input: canonical address length not correct",
@@ -133,17 +260,97 @@ pub fn mock_env<U: Into<HumanAddr>>(sender: U, sent: &[Coin]) -> Env {
     }
 }
 
+/// The time between two blocks when advancing blocks with MockEnvBuilder
+pub const MOCK_BLOCK_TIME: u64 = 5;
+
+/// Builds the Env of a test, starting from the values of mock_env. The builder can be kept
+/// to build the Env of later blocks:
+///
+/// ```ignore
+/// let mut builder = MockEnvBuilder::new("creator", &[]);
+/// let init_env = builder.height(100).build();
+/// let handle_env = builder.advance_blocks(10).sender("user").build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockEnvBuilder {
+    env: Env,
+}
+
+impl MockEnvBuilder {
+    pub fn new<U: Into<HumanAddr>>(sender: U, sent: &[Coin]) -> Self {
+        MockEnvBuilder {
+            env: mock_env(sender, sent),
+        }
+    }
+
+    pub fn sender<U: Into<HumanAddr>>(&mut self, sender: U) -> &mut Self {
+        self.env.message.sender = sender.into();
+        self
+    }
+
+    pub fn sent_funds(&mut self, sent: &[Coin]) -> &mut Self {
+        self.env.message.sent_funds = sent.to_vec();
+        self
+    }
+
+    pub fn height(&mut self, height: u64) -> &mut Self {
+        self.env.block.height = height;
+        self
+    }
+
+    /// Sets the block time in seconds
+    pub fn time(&mut self, time: u64) -> &mut Self {
+        self.env.block.time = time;
+        self
+    }
+
+    pub fn chain_id(&mut self, chain_id: &str) -> &mut Self {
+        self.env.block.chain_id = chain_id.to_string();
+        self
+    }
+
+    pub fn contract<U: Into<HumanAddr>>(&mut self, address: U) -> &mut Self {
+        self.env.contract.address = address.into();
+        self
+    }
+
+    pub fn code_hash(&mut self, code_hash: &str) -> &mut Self {
+        self.env.contract_code_hash = code_hash.to_string();
+        self
+    }
+
+    pub fn contract_key(&mut self, contract_key: Option<&str>) -> &mut Self {
+        self.env.contract_key = contract_key.map(String::from);
+        self
+    }
+
+    /// Moves to a later block, MOCK_BLOCK_TIME seconds per block
+    pub fn advance_blocks(&mut self, blocks: u64) -> &mut Self {
+        self.env.block.height += blocks;
+        self.env.block.time += blocks * MOCK_BLOCK_TIME;
+        self
+    }
+
+    /// Moves the block time forward, without changing the height
+    pub fn advance_time(&mut self, seconds: u64) -> &mut Self {
+        self.env.block.time += seconds;
+        self
+    }
+
+    pub fn build(&self) -> Env {
+        self.env.clone()
+    }
+}
+
 /// The same type as cosmwasm-std's QuerierResult, but easier to reuse in
 /// cosmwasm-vm. It might diverge from QuerierResult at some point.
 pub type MockQuerierCustomHandlerResult = SystemResult<StdResult<Binary>>;
 
-/// MockQuerier holds an immutable table of bank balances
-/// TODO: also allow querying contracts
+/// MockQuerier holds an immutable table of bank balances and the contracts that can be queried
 pub struct MockQuerier<C: DeserializeOwned = Empty> {
     bank: BankQuerier,
     staking: StakingQuerier,
-    // placeholder to add support later
-    wasm: NoWasmQuerier,
+    wasm: WasmQuerier,
     dist: DistQuerier,
     mint: MintQuerier,
     gov: GovQuerier,
@@ -151,7 +358,7 @@ pub struct MockQuerier<C: DeserializeOwned = Empty> {
     /// always errors by default. Update it via `with_custom_handler`.
     ///
     /// Use box to avoid the need of another generic type
-    custom_handler: Box<dyn for<'a> Fn(&'a C) -> MockQuerierCustomHandlerResult>,
+    custom_handler: MockCustomHandler<C>,
 }
 
 impl<C: DeserializeOwned> MockQuerier<C> {
@@ -159,10 +366,10 @@ impl<C: DeserializeOwned> MockQuerier<C> {
         MockQuerier {
             bank: BankQuerier::new(balances),
             staking: StakingQuerier::default(),
-            wasm: NoWasmQuerier {},
-            dist: DistQuerier {},
-            mint: MintQuerier {},
-            gov: GovQuerier {},
+            wasm: WasmQuerier::default(),
+            dist: DistQuerier::default(),
+            mint: MintQuerier::default(),
+            gov: GovQuerier::default(),
             // strange argument notation suggested as a workaround here: https://github.com/rust-lang/rust/issues/41078#issuecomment-294296365
             custom_handler: Box::from(|_: &_| -> MockQuerierCustomHandlerResult {
                 Err(SystemError::UnsupportedRequest {
@@ -185,12 +392,40 @@ impl<C: DeserializeOwned> MockQuerier<C> {
     pub fn update_staking(
         &mut self,
         denom: &str,
-        validators: &[crate::query::Validator],
-        delegations: &[crate::query::FullDelegation],
+        validators: &[Validator],
+        delegations: &[FullDelegation],
     ) {
         self.staking = StakingQuerier::new(denom, validators, delegations);
     }
 
+    /// Sets the delegations being unbonded, update_staking clears them
+    #[cfg(feature = "staking")]
+    pub fn update_unbonding_delegations(&mut self, unbonding_delegations: &[Delegation]) {
+        self.staking.unbonding_delegations = unbonding_delegations.to_vec();
+    }
+
+    pub fn update_proposals(&mut self, proposals: &[Proposal]) {
+        self.gov = GovQuerier::new(proposals);
+    }
+
+    pub fn update_inflation(&mut self, inflation: &str) {
+        self.mint.inflation = inflation.to_string();
+    }
+
+    pub fn update_bonded_ratio(&mut self, bonded_ratio: &str) {
+        self.mint.bonded_ratio = bonded_ratio.to_string();
+    }
+
+    // set the rewards of the given delegator per validator and return the old rewards,
+    // the total is computed from them
+    pub fn update_rewards<U: Into<HumanAddr>>(
+        &mut self,
+        delegator: U,
+        rewards: Vec<ValidatorRewards>,
+    ) -> Option<Vec<ValidatorRewards>> {
+        self.dist.rewards.insert(delegator.into(), rewards)
+    }
+
     pub fn with_custom_handler<CH: 'static>(mut self, handler: CH) -> Self
     where
         CH: Fn(&C) -> MockQuerierCustomHandlerResult,
@@ -198,6 +433,70 @@ impl<C: DeserializeOwned> MockQuerier<C> {
         self.custom_handler = Box::from(handler);
         self
     }
+
+    /// Answers all wasm queries with the given handler, instead of the registered contracts
+    pub fn with_wasm_handler<WH>(mut self, handler: WH) -> Self
+    where
+        WH: Fn(&WasmQuery) -> MockQuerierCustomHandlerResult + 'static,
+    {
+        self.wasm.handler = Some(Box::from(handler));
+        self
+    }
+
+    /// Sets the response of a contract to the given smart query, registering the contract
+    /// if needed. The message must serialize to the same JSON as the one the contract sends.
+    pub fn update_wasm_smart<U: Into<HumanAddr>, M: Serialize, R: Serialize>(
+        &mut self,
+        contract_addr: U,
+        msg: &M,
+        response: &R,
+    ) -> StdResult<()> {
+        let msg = to_vec(msg)?;
+        let response = to_binary(response)?;
+        self.wasm
+            .contracts
+            .entry(contract_addr.into())
+            .or_default()
+            .smart
+            .insert(msg, response);
+        Ok(())
+    }
+
+    /// Sets a raw value in the storage of a contract, registering the contract if needed,
+    /// and returns the old value
+    pub fn update_wasm_raw<U: Into<HumanAddr>, K: Into<Binary>, V: Into<Binary>>(
+        &mut self,
+        contract_addr: U,
+        key: K,
+        value: V,
+    ) -> Option<Binary> {
+        self.wasm
+            .contracts
+            .entry(contract_addr.into())
+            .or_default()
+            .raw
+            .insert(key.into().0, value.into())
+    }
+
+    /// Answers the smart queries to a contract that have no canned response with the given
+    /// function, usually a wrapper around the native `query` of that contract:
+    ///
+    /// ```ignore
+    /// let other = mock_dependencies(20, &[]);
+    /// querier.update_wasm_contract("other", move |msg| {
+    ///     other_contract::query(&other, mock_env("other", &[]), from_binary(msg)?)
+    /// });
+    /// ```
+    pub fn update_wasm_contract<U: Into<HumanAddr>, F>(&mut self, contract_addr: U, query: F)
+    where
+        F: Fn(&Binary) -> StdResult<Binary> + 'static,
+    {
+        self.wasm
+            .contracts
+            .entry(contract_addr.into())
+            .or_default()
+            .query = Some(Box::from(query));
+    }
 }
 
 impl<C: DeserializeOwned> Querier for MockQuerier<C> {
@@ -229,51 +528,168 @@ impl<C: DeserializeOwned> MockQuerier<C> {
     }
 }
 
-#[derive(Clone, Default)]
-struct NoWasmQuerier {
-    // FIXME: actually provide a way to call out
+/// A handler answering the custom queries, see `MockQuerier::with_custom_handler`
+pub type MockCustomHandler<C> = Box<dyn for<'a> Fn(&'a C) -> MockQuerierCustomHandlerResult>;
+
+/// A handler answering all wasm queries, see `MockQuerier::with_wasm_handler`
+pub type MockWasmHandler = Box<dyn for<'a> Fn(&'a WasmQuery) -> MockQuerierCustomHandlerResult>;
+
+/// The native query function of a contract, taking the JSON encoded query message
+pub type MockContractQuery = Box<dyn for<'a> Fn(&'a Binary) -> StdResult<Binary>>;
+
+/// WasmQuerier answers queries to the contracts registered on it, either with canned responses
+/// or by calling their native query function. Queries to any other address fail with
+/// `SystemError::NoSuchContract`, as they would on chain.
+#[derive(Default)]
+pub struct WasmQuerier {
+    contracts: HashMap<HumanAddr, MockContract>,
+    /// Replaces the registry when set
+    handler: Option<MockWasmHandler>,
+}
+
+#[derive(Default)]
+struct MockContract {
+    /// Responses by JSON encoded query message
+    smart: HashMap<Vec<u8>, Binary>,
+    /// The raw storage
+    raw: HashMap<Vec<u8>, Binary>,
+    /// Answers the smart queries without a canned response
+    query: Option<MockContractQuery>,
 }
 
-impl NoWasmQuerier {
-    fn query(&self, request: &WasmQuery) -> QuerierResult {
-        let addr = match request {
+impl WasmQuerier {
+    pub fn query(&self, request: &WasmQuery) -> QuerierResult {
+        if let Some(handler) = &self.handler {
+            return handler(request);
+        }
+
+        let contract_addr = match request {
             WasmQuery::Smart { contract_addr, .. } => contract_addr,
             WasmQuery::Raw { contract_addr, .. } => contract_addr,
+        };
+        let contract = match self.contracts.get(contract_addr) {
+            Some(contract) => contract,
+            None => {
+                return Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+        };
+
+        match request {
+            WasmQuery::Smart { msg, .. } => {
+                if let Some(response) = contract.smart.get(msg.as_slice()) {
+                    return Ok(Ok(response.clone()));
+                }
+                match &contract.query {
+                    Some(query) => Ok(query(msg)),
+                    None => Ok(Err(StdError::generic_err(format!(
+                        "No mock response for query {} to {}",
+                        String::from_utf8_lossy(msg.as_slice()),
+                        contract_addr
+                    )))),
+                }
+            }
+            // like the chain, a missing key reads as an empty value
+            WasmQuery::Raw { key, .. } => Ok(Ok(contract
+                .raw
+                .get(key.as_slice())
+                .cloned()
+                .unwrap_or_default())),
         }
-        .clone();
-        Err(SystemError::NoSuchContract { addr })
     }
 }
 
 #[derive(Clone, Default)]
-pub struct GovQuerier {}
+pub struct GovQuerier {
+    proposals: Vec<Proposal>,
+}
 
 impl GovQuerier {
-    pub fn query(&self, _request: &GovQuery) -> QuerierResult {
-        QuerierResult::Ok(Ok(Binary::default()))
+    pub fn new(proposals: &[Proposal]) -> Self {
+        GovQuerier {
+            proposals: proposals.to_vec(),
+        }
+    }
+
+    pub fn query(&self, request: &GovQuery) -> QuerierResult {
+        match request {
+            GovQuery::Proposals {} => {
+                let res = ProposalsResponse {
+                    proposals: self.proposals.clone(),
+                };
+                Ok(to_binary(&res))
+            }
+        }
     }
 }
 
-#[derive(Clone, Default)]
-pub struct MintQuerier {}
+#[derive(Clone)]
+pub struct MintQuerier {
+    inflation: String,
+    bonded_ratio: String,
+}
 
 impl MintQuerier {
-    pub fn query(&self, _request: &MintQuery) -> QuerierResult {
-        QuerierResult::Ok(Ok(Binary::default()))
+    pub fn new(inflation: &str, bonded_ratio: &str) -> Self {
+        MintQuerier {
+            inflation: inflation.to_string(),
+            bonded_ratio: bonded_ratio.to_string(),
+        }
+    }
+
+    pub fn query(&self, request: &MintQuery) -> QuerierResult {
+        match request {
+            MintQuery::Inflation {} => {
+                let res = InflationResponse {
+                    inflation_rate: self.inflation.clone(),
+                };
+                Ok(to_binary(&res))
+            }
+            MintQuery::BondedRatio {} => {
+                let res = BondedRatioResponse {
+                    bonded_ratio: self.bonded_ratio.clone(),
+                };
+                Ok(to_binary(&res))
+            }
+        }
+    }
+}
+
+impl Default for MintQuerier {
+    fn default() -> Self {
+        MintQuerier::new("0", "0")
     }
 }
 
 #[derive(Clone, Default)]
-pub struct DistQuerier {}
+pub struct DistQuerier {
+    rewards: HashMap<HumanAddr, Vec<ValidatorRewards>>,
+}
 
 impl DistQuerier {
+    pub fn new(rewards: &[(&HumanAddr, &[ValidatorRewards])]) -> Self {
+        let mut map = HashMap::new();
+        for (delegator, validator_rewards) in rewards.iter() {
+            map.insert(HumanAddr::from(delegator), validator_rewards.to_vec());
+        }
+        DistQuerier { rewards: map }
+    }
+
     pub fn query(&self, request: &DistQuery) -> QuerierResult {
         match request {
-            DistQuery::Rewards { .. } => {
-                // proper error on not found, serialize result on found
+            DistQuery::Rewards { delegator } => {
+                // a delegator without rewards gets an empty response
+                let rewards = self.rewards.get(delegator).cloned().unwrap_or_default();
+                let mut total = Coins::new();
+                for coin in rewards.iter().flat_map(|r| r.reward.iter()) {
+                    if let Err(e) = total.add(coin.clone()) {
+                        return Ok(Err(e));
+                    }
+                }
                 let resp = RewardsResponse {
-                    rewards: vec![],
-                    total: vec![],
+                    rewards,
+                    total: total.to_vec(),
                 };
                 Ok(to_binary(&resp))
             }
@@ -328,6 +744,7 @@ pub struct StakingQuerier {
     denom: String,
     validators: Vec<Validator>,
     delegations: Vec<FullDelegation>,
+    unbonding_delegations: Vec<Delegation>,
 }
 
 impl StakingQuerier {
@@ -336,9 +753,16 @@ impl StakingQuerier {
             denom: denom.to_string(),
             validators: validators.to_vec(),
             delegations: delegations.to_vec(),
+            unbonding_delegations: vec![],
         }
     }
 
+    /// Sets the delegations that are being unbonded. They are no longer part of the delegations.
+    pub fn with_unbonding_delegations(mut self, unbonding_delegations: &[Delegation]) -> Self {
+        self.unbonding_delegations = unbonding_delegations.to_vec();
+        self
+    }
+
     pub fn query(&self, request: &StakingQuery) -> QuerierResult {
         match request {
             StakingQuery::BondedDenom {} => {
@@ -379,11 +803,10 @@ impl StakingQuerier {
             }
             StakingQuery::UnbondingDelegations { delegator } => {
                 let delegations: Vec<_> = self
-                    .delegations
+                    .unbonding_delegations
                     .iter()
                     .filter(|d| &d.delegator == delegator)
                     .cloned()
-                    .map(|d| d.into())
                     .collect();
                 let res = UnbondingDelegationsResponse { delegations };
                 Ok(to_binary(&res))
@@ -395,8 +818,9 @@ impl StakingQuerier {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::query::Delegation;
+    use crate::traits::Storage;
     use crate::{coin, coins, from_binary, Decimal, HumanAddr};
+    use serde::Deserialize;
 
     #[test]
     fn mock_env_arguments() {
@@ -412,6 +836,75 @@ mod test {
         assert_eq!(a, c);
     }
 
+    #[test]
+    fn mock_env_builder_works() {
+        let mut builder = MockEnvBuilder::new("creator", &coins(10, "uscrt"));
+        assert_eq!(builder.build(), mock_env("creator", &coins(10, "uscrt")));
+
+        let env = builder
+            .height(100)
+            .time(1_000)
+            .chain_id("secret-4")
+            .contract("contract")
+            .code_hash("1234abcd")
+            .contract_key(None)
+            .build();
+        assert_eq!(env.block.height, 100);
+        assert_eq!(env.block.time, 1_000);
+        assert_eq!(env.block.chain_id, "secret-4");
+        assert_eq!(env.contract.address, HumanAddr::from("contract"));
+        assert_eq!(env.contract_code_hash, "1234abcd");
+        assert_eq!(env.contract_key, None);
+
+        let env = builder
+            .advance_blocks(3)
+            .sender("user")
+            .sent_funds(&[])
+            .build();
+        assert_eq!(env.block.height, 103);
+        assert_eq!(env.block.time, 1_000 + 3 * MOCK_BLOCK_TIME);
+        assert_eq!(env.message.sender, HumanAddr::from("user"));
+        assert_eq!(env.message.sent_funds, vec![]);
+        let env = builder.advance_time(60).build();
+        assert_eq!(env.block.height, 103);
+        assert_eq!(env.block.time, 1_075);
+    }
+
+    #[test]
+    fn mock_dependencies_builder_works() {
+        let deps = MockDependenciesBuilder::new()
+            .contract_balance(&coins(100, "uscrt"))
+            .balance("alice", &coins(5, "uscrt"))
+            .balance("alice", &coins(7, "uatom"))
+            .build();
+        let balance = deps.querier.query_all_balances(MOCK_CONTRACT_ADDR).unwrap();
+        assert_eq!(balance, coins(100, "uscrt"));
+        let balance = deps.querier.query_all_balances("alice").unwrap();
+        assert_eq!(balance, coins(7, "uatom"));
+        let canonical = deps
+            .api
+            .canonical_address(&HumanAddr::from("alice"))
+            .unwrap();
+        assert_eq!(canonical.len(), 20);
+
+        let deps = MockDependenciesBuilder::new().bech32("secret").build();
+        assert!(deps.api.addr_validate("alice").is_err());
+    }
+
+    #[test]
+    fn mock_dependencies_builder_sets_custom_handler() {
+        #[derive(Serialize, Deserialize)]
+        struct Price {}
+
+        let deps = MockDependenciesBuilder::new()
+            .canonical_length(32)
+            .custom_handler(|_: &Price| Ok(to_binary(&42u64)))
+            .build();
+        let request = QueryRequest::Custom(Price {});
+        let price: u64 = deps.querier.custom_query(&request).unwrap();
+        assert_eq!(price, 42);
+    }
+
     #[test]
     fn flip_addresses() {
         let api = MockApi::new(20);
@@ -425,6 +918,48 @@ mod test {
         assert_eq!(human, recovered);
     }
 
+    #[test]
+    fn addr_validate_works() {
+        let api = MockApi::new(20);
+        let addr = api.addr_validate("shorty").unwrap();
+        assert_eq!(addr.as_str(), "shorty");
+        assert_eq!(HumanAddr::from(addr), HumanAddr::from("shorty"));
+
+        // format errors of canonical_address are passed on
+        let err = api.addr_validate("1").unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Invalid input: human address too short")
+        );
+        let err = api.addr_validate("").unwrap_err();
+        assert_eq!(err, StdError::generic_err("Invalid input: empty address"));
+        // the zero byte is lost on the way back
+        let err = api.addr_validate("sho\0rty").unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Invalid input: address not normalized")
+        );
+    }
+
+    #[test]
+    fn bech32_addresses_round_trip() {
+        let api = MockApi::new_bech32("secret");
+        // the zero padding mode would lose these zero bytes
+        let mut bytes = vec![0u8; 20];
+        bytes[1] = 7;
+        bytes[4] = 9;
+        let canon = CanonicalAddr(Binary(bytes));
+        let human = api.human_address(&canon).unwrap();
+        assert!(human.as_str().starts_with("secret1"));
+        assert_eq!(api.canonical_address(&human).unwrap(), canon);
+        assert_eq!(
+            human,
+            Bech32Api::new("secret").human_address(&canon).unwrap()
+        );
+
+        assert!(api.canonical_address(&HumanAddr::from("shorty")).is_err());
+    }
+
     #[test]
     #[should_panic(expected = "length not correct")]
     fn human_address_input_length() {
@@ -523,6 +1058,197 @@ mod test {
         assert_eq!(res.amount, coin(0, "ELF"));
     }
 
+    #[test]
+    fn querier_helpers_decode_mock_responses() {
+        let querier: MockQuerier = MockQuerier::new(&[]);
+        let rewards = querier.query_rewards("delegator").unwrap();
+        assert_eq!(rewards.rewards, vec![]);
+        assert_eq!(rewards.total, vec![]);
+        assert_eq!(querier.query_inflation().unwrap(), "0");
+        assert_eq!(querier.query_bonded_ratio().unwrap(), "0");
+        assert_eq!(querier.query_proposals().unwrap(), vec![]);
+
+        let err = querier
+            .query_wasm_smart::<Empty, _, _>("other", "hash", &Empty {})
+            .unwrap_err();
+        assert!(err.to_string().contains("No such contract: other"));
+        let err = querier.query_wasm_raw("other", "hash", b"key").unwrap_err();
+        assert!(err.to_string().contains("No such contract: other"));
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum OtherQueryMsg {
+        Owner {},
+        Counter { offset: u64 },
+    }
+
+    fn other_query<S: Storage, A: Api, Q: Querier>(
+        deps: &Extern<S, A, Q>,
+        _env: Env,
+        msg: OtherQueryMsg,
+    ) -> StdResult<Binary> {
+        match msg {
+            OtherQueryMsg::Owner {} => to_binary(&HumanAddr::from("owner")),
+            OtherQueryMsg::Counter { offset } => {
+                let counter = deps.storage.get(b"counter").map_or(0, |v| v[0] as u64);
+                to_binary(&(counter + offset))
+            }
+        }
+    }
+
+    #[test]
+    fn wasm_querier_answers_canned_queries() {
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier
+            .update_wasm_smart("other", &OtherQueryMsg::Owner {}, &"admin")
+            .unwrap();
+        assert_eq!(querier.update_wasm_raw("other", b"config", b"raw"), None);
+
+        let owner: HumanAddr = querier
+            .query_wasm_smart("other", "hash", &OtherQueryMsg::Owner {})
+            .unwrap();
+        assert_eq!(owner, HumanAddr::from("admin"));
+        let err = querier
+            .query_wasm_smart::<u64, _, _>("other", "hash", &OtherQueryMsg::Counter { offset: 1 })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(
+                r#"No mock response for query {"counter":{"offset":1}} to other"#
+            )
+        );
+
+        let raw = querier.query_wasm_raw("other", "hash", b"config").unwrap();
+        assert_eq!(raw, Some(b"raw".to_vec()));
+        assert_eq!(
+            querier.query_wasm_raw("other", "hash", b"missing").unwrap(),
+            None
+        );
+        let missing = WasmQuery::Raw {
+            contract_addr: HumanAddr::from("other"),
+            key: Binary::from(b"missing"),
+            callback_code_hash: "hash".to_string(),
+        };
+        assert_eq!(
+            querier.handle_query(&missing.into()),
+            Ok(Ok(Binary::default()))
+        );
+        assert!(querier
+            .query_wasm_raw("unknown", "hash", b"config")
+            .is_err());
+    }
+
+    #[test]
+    fn wasm_querier_calls_native_query() {
+        let mut other = mock_dependencies(20, &[]);
+        other.storage.set(b"counter", &[5]);
+
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier.update_wasm_contract("other", move |msg| {
+            other_query(&other, mock_env("other", &[]), from_binary(msg)?)
+        });
+        // canned responses take precedence
+        querier
+            .update_wasm_smart("other", &OtherQueryMsg::Owner {}, &"admin")
+            .unwrap();
+
+        let counter: u64 = querier
+            .query_wasm_smart("other", "hash", &OtherQueryMsg::Counter { offset: 2 })
+            .unwrap();
+        assert_eq!(counter, 7);
+        let owner: HumanAddr = querier
+            .query_wasm_smart("other", "hash", &OtherQueryMsg::Owner {})
+            .unwrap();
+        assert_eq!(owner, HumanAddr::from("admin"));
+    }
+
+    #[test]
+    fn wasm_querier_uses_handler() {
+        let querier: MockQuerier =
+            MockQuerier::new(&[]).with_wasm_handler(|request| match request {
+                WasmQuery::Smart { contract_addr, .. } => Ok(to_binary(contract_addr)),
+                WasmQuery::Raw { .. } => Err(SystemError::UnsupportedRequest {
+                    kind: "raw".to_string(),
+                }),
+            });
+
+        let addr: HumanAddr = querier
+            .query_wasm_smart("anything", "hash", &OtherQueryMsg::Owner {})
+            .unwrap();
+        assert_eq!(addr, HumanAddr::from("anything"));
+        assert!(querier.query_wasm_raw("anything", "hash", b"key").is_err());
+    }
+
+    #[cfg(feature = "staking")]
+    #[test]
+    fn querier_helpers_query_unbonding_delegations() {
+        let delegations = vec![FullDelegation {
+            delegator: HumanAddr::from("delegator"),
+            validator: HumanAddr::from("validator"),
+            amount: coin(100, "ustake"),
+            can_redelegate: coin(100, "ustake"),
+            accumulated_rewards: coin(5, "ustake"),
+        }];
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        querier.update_staking("ustake", &[], &delegations);
+        // active delegations are not unbonding
+        let unbonding = querier.query_unbonding_delegations("delegator").unwrap();
+        assert_eq!(unbonding, vec![]);
+
+        let unbonding = vec![Delegation {
+            delegator: HumanAddr::from("delegator"),
+            validator: HumanAddr::from("validator"),
+            amount: coin(40, "ustake"),
+        }];
+        querier.update_unbonding_delegations(&unbonding);
+        let res = querier.query_unbonding_delegations("delegator").unwrap();
+        assert_eq!(res, unbonding);
+        let res = querier.query_unbonding_delegations("other").unwrap();
+        assert_eq!(res, vec![]);
+    }
+
+    #[test]
+    fn gov_and_mint_queriers_can_be_updated() {
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        let proposals = vec![Proposal {
+            id: 7,
+            voting_start_time: 100,
+            voting_end_time: 200,
+        }];
+        querier.update_proposals(&proposals);
+        querier.update_inflation("0.13");
+        querier.update_bonded_ratio("0.67");
+
+        assert_eq!(querier.query_proposals().unwrap(), proposals);
+        assert_eq!(querier.query_inflation().unwrap(), "0.13");
+        assert_eq!(querier.query_bonded_ratio().unwrap(), "0.67");
+    }
+
+    #[test]
+    fn dist_querier_sums_rewards() {
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        let rewards = vec![
+            ValidatorRewards {
+                validator_address: HumanAddr::from("val1"),
+                reward: vec![coin(10, "uscrt"), coin(3, "uatom")],
+            },
+            ValidatorRewards {
+                validator_address: HumanAddr::from("val2"),
+                reward: vec![coin(5, "uscrt")],
+            },
+        ];
+        assert_eq!(querier.update_rewards("delegator", rewards.clone()), None);
+
+        let res = querier.query_rewards("delegator").unwrap();
+        assert_eq!(res.rewards, rewards);
+        assert_eq!(res.total, vec![coin(3, "uatom"), coin(15, "uscrt")]);
+
+        let res = querier.query_rewards("other").unwrap();
+        assert_eq!(res.rewards, vec![]);
+        assert_eq!(res.total, vec![]);
+    }
+
     #[test]
     fn staking_querier_validators() {
         let val1 = Validator {
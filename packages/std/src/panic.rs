@@ -0,0 +1,30 @@
+//! panic reports contract panics to the host before the module traps.
+//!
+//! Without this hook a Rust panic only surfaces as an `unreachable` trap in the VM
+//! and the panic message is lost.
+use std::panic;
+use std::sync::Once;
+
+use crate::memory::{build_region, Region};
+
+extern "C" {
+    /// Sends the panic message to the host. The host traps the execution,
+    /// so this call never returns.
+    fn abort(message: u32);
+}
+
+static INSTALL_PANIC_HANDLER: Once = Once::new();
+
+/// install_panic_handler sets a panic hook that forwards the panic message (including its location)
+/// to the host via the `abort` import. The export helpers call this on every entry point,
+/// the hook is only set by the first call.
+pub fn install_panic_handler() {
+    INSTALL_PANIC_HANDLER.call_once(|| {
+        panic::set_hook(Box::new(|info| {
+            let message = info.to_string();
+            let region = build_region(message.as_bytes());
+            let message_ptr = &*region as *const Region as u32;
+            unsafe { abort(message_ptr) };
+        }));
+    });
+}
@@ -0,0 +1,148 @@
+//! Helpers to validate the funds sent with a message
+//!
+//! They all fail with `StdError::InvalidPayment`, so clients can match on the `PaymentError`.
+
+use crate::coins::{Coin, Coins};
+use crate::errors::{PaymentError, StdError, StdResult};
+use crate::math::Uint128;
+use crate::types::MessageInfo;
+
+/// Normalizes the sent funds, so coins with a zero amount are not considered as sent
+fn sent_coins(info: &MessageInfo) -> StdResult<Coins> {
+    info.sent_coins()
+}
+
+/// nonpayable fails if any funds were sent
+pub fn nonpayable(info: &MessageInfo) -> StdResult<()> {
+    if sent_coins(info)?.is_empty() {
+        Ok(())
+    } else {
+        Err(StdError::invalid_payment(PaymentError::NonPayable {}))
+    }
+}
+
+/// must_pay requires that exactly one denom was sent and that it is the given one.
+/// Returns the amount that was sent.
+pub fn must_pay(info: &MessageInfo, denom: &str) -> StdResult<Uint128> {
+    let coins = sent_coins(info)?;
+    match coins.len() {
+        0 => Err(StdError::invalid_payment(PaymentError::NoFunds {})),
+        1 if coins.denoms()[0] == denom => Ok(coins.amount_of(denom)),
+        1 => Err(StdError::invalid_payment(PaymentError::MissingDenom {
+            denom: denom.to_string(),
+        })),
+        _ => Err(StdError::invalid_payment(PaymentError::MultipleDenoms {})),
+    }
+}
+
+/// may_pay accepts no funds, or funds of the given denom only.
+/// Returns the amount that was sent, which is zero if nothing was.
+pub fn may_pay(info: &MessageInfo, denom: &str) -> StdResult<Uint128> {
+    let coins = sent_coins(info)?;
+    match coins.len() {
+        0 => Ok(Uint128::zero()),
+        1 if coins.denoms()[0] == denom => Ok(coins.amount_of(denom)),
+        1 => Err(StdError::invalid_payment(PaymentError::ExtraDenom {
+            denom: coins.denoms()[0].to_string(),
+        })),
+        _ => Err(StdError::invalid_payment(PaymentError::MultipleDenoms {})),
+    }
+}
+
+/// must_pay_at_least works like must_pay, and also requires the amount sent to be
+/// at least the required one. Returns the amount that was sent.
+pub fn must_pay_at_least(info: &MessageInfo, required: &Coin) -> StdResult<Uint128> {
+    let amount = must_pay(info, &required.denom)?;
+    if amount < required.amount {
+        return Err(StdError::invalid_payment(PaymentError::InsufficientFunds {
+            required: required.clone(),
+            sent: Coin {
+                denom: required.denom.clone(),
+                amount,
+            },
+        }));
+    }
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::addresses::HumanAddr;
+    use crate::coins::coin;
+
+    fn info(sent_funds: Vec<Coin>) -> MessageInfo {
+        MessageInfo {
+            sender: HumanAddr::from("sender"),
+            sent_funds,
+        }
+    }
+
+    #[test]
+    fn nonpayable_works() {
+        assert_eq!(nonpayable(&info(vec![])), Ok(()));
+        // zero amounts are not funds
+        assert_eq!(nonpayable(&info(vec![coin(0, "uscrt")])), Ok(()));
+        assert_eq!(
+            nonpayable(&info(vec![coin(1, "uscrt")])),
+            Err(StdError::invalid_payment(PaymentError::NonPayable {}))
+        );
+    }
+
+    #[test]
+    fn must_pay_works() {
+        assert_eq!(
+            must_pay(&info(vec![coin(7, "uscrt")]), "uscrt"),
+            Ok(Uint128(7))
+        );
+        assert_eq!(
+            must_pay(&info(vec![]), "uscrt"),
+            Err(StdError::invalid_payment(PaymentError::NoFunds {}))
+        );
+        assert_eq!(
+            must_pay(&info(vec![coin(7, "uatom")]), "uscrt"),
+            Err(StdError::invalid_payment(PaymentError::MissingDenom {
+                denom: "uscrt".to_string()
+            }))
+        );
+        assert_eq!(
+            must_pay(&info(vec![coin(7, "uscrt"), coin(1, "uatom")]), "uscrt"),
+            Err(StdError::invalid_payment(PaymentError::MultipleDenoms {}))
+        );
+    }
+
+    #[test]
+    fn may_pay_works() {
+        assert_eq!(may_pay(&info(vec![]), "uscrt"), Ok(Uint128::zero()));
+        assert_eq!(
+            may_pay(&info(vec![coin(7, "uscrt")]), "uscrt"),
+            Ok(Uint128(7))
+        );
+        assert_eq!(
+            may_pay(&info(vec![coin(7, "uatom")]), "uscrt"),
+            Err(StdError::invalid_payment(PaymentError::ExtraDenom {
+                denom: "uatom".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn must_pay_at_least_works() {
+        let required = coin(100, "uscrt");
+        assert_eq!(
+            must_pay_at_least(&info(vec![coin(150, "uscrt")]), &required),
+            Ok(Uint128(150))
+        );
+        assert_eq!(
+            must_pay_at_least(&info(vec![coin(99, "uscrt")]), &required),
+            Err(StdError::invalid_payment(PaymentError::InsufficientFunds {
+                required: required.clone(),
+                sent: coin(99, "uscrt"),
+            }))
+        );
+        assert_eq!(
+            must_pay_at_least(&info(vec![]), &required),
+            Err(StdError::invalid_payment(PaymentError::NoFunds {}))
+        );
+    }
+}
@@ -12,6 +12,7 @@ pub type QueryResponse = Binary;
 pub type QueryResult = StdResult<QueryResponse>;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum QueryRequest<T> {
     Bank(BankQuery),
@@ -24,6 +25,7 @@ pub enum QueryRequest<T> {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum BankQuery {
     /// This calls into the native bank module for one denomination
@@ -36,6 +38,7 @@ pub enum BankQuery {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum GovQuery {
     /// Returns all the currently active proposals. Might be useful to filter out invalid votes, and trigger
@@ -44,6 +47,7 @@ pub enum GovQuery {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DistQuery {
     /// This calls into the native bank module for all denominations.
@@ -53,6 +57,7 @@ pub enum DistQuery {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum MintQuery {
     /// This calls into the native bank module for all denominations.
@@ -63,6 +68,7 @@ pub enum MintQuery {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WasmQuery {
     /// this queries the public API of another contract at a known address (with known ABI)
@@ -76,7 +82,7 @@ pub enum WasmQuery {
         msg: Binary,
     },
     /// this queries the raw kv-store of the contract.
-    /// returns the raw, unparsed data stored at that key (or `Ok(Err(StdError:NotFound{}))` if missing)
+    /// returns the raw, unparsed data stored at that key (or an empty value if missing)
     Raw {
         contract_addr: HumanAddr,
         /// Key is the raw key used in the contracts Storage
@@ -125,6 +131,7 @@ impl<T: Clone + fmt::Debug + PartialEq> From<WasmQuery> for QueryRequest<T> {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct BalanceResponse {
     /// Always returns a Coin with the requested denom.
@@ -133,6 +140,7 @@ pub struct BalanceResponse {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct AllBalanceResponse {
     /// Returns all non-zero coins held by this account.
@@ -140,6 +148,7 @@ pub struct AllBalanceResponse {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum StakingQuery {
     /// Returns the denomination that can be bonded (if there are multiple native tokens on the chain)
@@ -160,6 +169,7 @@ pub enum StakingQuery {
 
 /// ProposalsResponse is data format returned from GovQuery::Proposals query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct ProposalsResponse {
     pub proposals: Vec<Proposal>,
@@ -167,6 +177,7 @@ pub struct ProposalsResponse {
 
 /// ProposalsResponse is data format returned from GovQuery::Proposals query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Proposal {
     pub id: u64,
@@ -178,6 +189,7 @@ pub struct Proposal {
 
 /// BondedDenomResponse is data format returned from StakingRequest::BondedDenom query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct BondedDenomResponse {
     pub denom: String,
@@ -185,6 +197,7 @@ pub struct BondedDenomResponse {
 
 /// UnbondingDelegationsResponse is data format returned from StakingRequest::UnbondingDelegations query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct UnbondingDelegationsResponse {
     pub delegations: Vec<Delegation>,
@@ -192,6 +205,7 @@ pub struct UnbondingDelegationsResponse {
 
 /// DelegationsResponse is data format returned from StakingRequest::AllDelegations query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct AllDelegationsResponse {
     pub delegations: Vec<Delegation>,
@@ -199,6 +213,7 @@ pub struct AllDelegationsResponse {
 
 /// Delegation is basic (cheap to query) data about a delegation
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Delegation {
     pub delegator: HumanAddr,
     pub validator: HumanAddr,
@@ -218,6 +233,7 @@ impl From<FullDelegation> for Delegation {
 
 /// DelegationResponse is data format returned from StakingRequest::Delegation query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct DelegationResponse {
     pub delegation: Option<FullDelegation>,
@@ -226,6 +242,7 @@ pub struct DelegationResponse {
 /// FullDelegation is all the info on the delegation, some (like accumulated_reward and can_redelegate)
 /// is expensive to query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FullDelegation {
     pub delegator: HumanAddr,
     pub validator: HumanAddr,
@@ -241,11 +258,13 @@ pub struct FullDelegation {
 
 /// ValidatorsResponse is data format returned from StakingRequest::Validators query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ValidatorsResponse {
     pub validators: Vec<Validator>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Validator {
     pub address: HumanAddr,
     pub commission: Decimal,
@@ -256,18 +275,21 @@ pub struct Validator {
 
 /// Rewards response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RewardsResponse {
     pub rewards: Vec<ValidatorRewards>,
     pub total: Vec<Coin>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ValidatorRewards {
     pub validator_address: HumanAddr,
     pub reward: Vec<Coin>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RewardCoin {
     pub coin: String,
     pub demon: String,
@@ -275,12 +297,14 @@ pub struct RewardCoin {
 
 /// Inflation response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct InflationResponse {
     pub inflation_rate: String,
 }
 
 /// Bonded Ratio response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BondedRatioResponse {
     pub bonded_ratio: String,
 }
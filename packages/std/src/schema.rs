@@ -0,0 +1,74 @@
+//! JsonSchema implementations for the types with a custom serialization,
+//! the others derive it when the `schema` feature is enabled.
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+
+use crate::coins::{Coin, Coins};
+use crate::encoding::Binary;
+use crate::math::{Decimal, Decimal256, Int128, SignedDecimal, Uint128, Uint256, Uint512};
+use crate::timestamp::Timestamp;
+
+/// Implements JsonSchema for types serialized as a string
+macro_rules! string_schema {
+    ($($name:ident),*) => {
+        $(
+            impl JsonSchema for $name {
+                fn schema_name() -> String {
+                    stringify!($name).to_string()
+                }
+
+                fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                    String::json_schema(gen)
+                }
+            }
+        )*
+    };
+}
+
+// Binary is base64, Timestamp is nanoseconds and the number types are decimal strings
+string_schema!(
+    Binary,
+    Timestamp,
+    Uint128,
+    Uint256,
+    Uint512,
+    Int128,
+    Decimal,
+    Decimal256,
+    SignedDecimal
+);
+
+/// Coins is serialized as a list of Coin
+impl JsonSchema for Coins {
+    fn schema_name() -> String {
+        "Coins".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Vec::<Coin>::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::init_handle::HandleResponse;
+    use schemars::schema_for;
+
+    #[test]
+    fn string_encoded_types_have_string_schemas() {
+        let schema = serde_json::to_value(schema_for!(Uint128)).unwrap();
+        assert_eq!(schema["type"], "string");
+        let schema = serde_json::to_value(schema_for!(Binary)).unwrap();
+        assert_eq!(schema["type"], "string");
+    }
+
+    #[test]
+    fn handle_response_schema_has_events() {
+        let schema = serde_json::to_value(schema_for!(HandleResponse)).unwrap();
+        assert!(schema["properties"]["events"].is_object());
+        assert!(schema["definitions"]["Event"].is_object());
+    }
+}
@@ -0,0 +1,1023 @@
+//! MockChain runs several contracts in process, through their native entry points, and executes
+//! the messages they return the way the chain would. It is meant for tests of contracts that
+//! send funds to or call each other.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+use crate::addresses::HumanAddr;
+use crate::coins::{Coin, Coins};
+use crate::encoding::Binary;
+use crate::errors::{StdError, StdResult, SystemError};
+use crate::init_handle::{plaintext_log, BankMsg, CosmosMsg, Event, HandleResponse, WasmMsg};
+#[cfg(feature = "iterator")]
+use crate::iterator::{Order, KV};
+use crate::mock::{mock_env, MockApi, MOCK_BLOCK_TIME};
+use crate::query::{AllBalanceResponse, BalanceResponse, BankQuery, QueryRequest, WasmQuery};
+use crate::serde::{from_binary, from_slice, to_binary, to_vec};
+use crate::storage::MemoryStorage;
+use crate::submessages::{Reply, ReplyOn, SubMsgExecutionResponse, SubMsgResult};
+use crate::traits::{Extern, Querier, QuerierResult, ReadonlyStorage, Storage};
+use crate::types::{BlockInfo, ContractInfo, Empty, Env, MessageInfo};
+
+/// The dependencies a contract gets on the MockChain
+pub type ChainDeps = Extern<ContractStorage, MockApi, ChainQuerier>;
+
+/// A contract that can be stored on the MockChain. The messages are JSON encoded,
+/// ContractWrapper implements this for the native entry points of a contract.
+pub trait MockChainContract {
+    fn init(&self, deps: &mut ChainDeps, env: Env, msg: &[u8]) -> StdResult<HandleResponse>;
+    fn handle(&self, deps: &mut ChainDeps, env: Env, msg: &[u8]) -> StdResult<HandleResponse>;
+    fn query(&self, deps: &ChainDeps, env: Env, msg: &[u8]) -> StdResult<Binary>;
+    fn reply(&self, deps: &mut ChainDeps, env: Env, msg: Reply) -> StdResult<HandleResponse>;
+}
+
+type InitFn<T, E> = fn(&mut ChainDeps, Env, T) -> Result<HandleResponse, E>;
+type QueryFn<T, E> = fn(&ChainDeps, Env, T) -> Result<Binary, E>;
+type ReplyFn<E> = fn(&mut ChainDeps, Env, Reply) -> Result<HandleResponse, E>;
+
+/// ContractWrapper holds the native entry points of a contract:
+///
+/// ```ignore
+/// let code_id = chain.store_code(ContractWrapper::new(
+///     contract::init,
+///     contract::handle,
+///     contract::query,
+/// ));
+/// ```
+///
+/// `init` returns a HandleResponse, like `handle`. The errors of init, handle and reply are
+/// of the same type, query can fail with another one.
+pub struct ContractWrapper<I, H, Q, E, QE> {
+    init: InitFn<I, E>,
+    handle: InitFn<H, E>,
+    query: QueryFn<Q, QE>,
+    reply: Option<ReplyFn<E>>,
+}
+
+impl<I, H, Q, E, QE> ContractWrapper<I, H, Q, E, QE> {
+    pub fn new(init: InitFn<I, E>, handle: InitFn<H, E>, query: QueryFn<Q, QE>) -> Self {
+        ContractWrapper {
+            init,
+            handle,
+            query,
+            reply: None,
+        }
+    }
+
+    /// Adds the reply entry point, needed by contracts sending submessages
+    pub fn with_reply(mut self, reply: ReplyFn<E>) -> Self {
+        self.reply = Some(reply);
+        self
+    }
+}
+
+impl<I, H, Q, E, QE> MockChainContract for ContractWrapper<I, H, Q, E, QE>
+where
+    I: DeserializeOwned,
+    H: DeserializeOwned,
+    Q: DeserializeOwned,
+    E: Into<StdError>,
+    QE: Into<StdError>,
+{
+    fn init(&self, deps: &mut ChainDeps, env: Env, msg: &[u8]) -> StdResult<HandleResponse> {
+        (self.init)(deps, env, from_slice(msg)?).map_err(Into::into)
+    }
+
+    fn handle(&self, deps: &mut ChainDeps, env: Env, msg: &[u8]) -> StdResult<HandleResponse> {
+        (self.handle)(deps, env, from_slice(msg)?).map_err(Into::into)
+    }
+
+    fn query(&self, deps: &ChainDeps, env: Env, msg: &[u8]) -> StdResult<Binary> {
+        (self.query)(deps, env, from_slice(msg)?).map_err(Into::into)
+    }
+
+    fn reply(&self, deps: &mut ChainDeps, env: Env, msg: Reply) -> StdResult<HandleResponse> {
+        match self.reply {
+            Some(reply) => reply(deps, env, msg).map_err(Into::into),
+            None => Err(StdError::generic_err("Contract does not export reply")),
+        }
+    }
+}
+
+/// The result of a transaction: the events of all contract calls and bank transfers,
+/// and the data returned by the called contract
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChainResponse {
+    pub events: Vec<Event>,
+    pub data: Option<Binary>,
+}
+
+impl ChainResponse {
+    /// Returns the first event of the given type emitted by the contract, e.g. "wasm" for its log
+    pub fn contract_event(&self, ty: &str, contract: &HumanAddr) -> Option<&Event> {
+        self.events.iter().find(|event| {
+            event.ty == ty
+                && event.attributes.first() == Some(&plaintext_log("_contract_address", contract))
+        })
+    }
+}
+
+#[derive(Clone)]
+struct ContractInstance {
+    code_id: u64,
+    label: String,
+}
+
+/// Everything that is rolled back when a transaction fails
+#[derive(Clone)]
+struct ChainState {
+    block: BlockInfo,
+    codes: Vec<Rc<dyn MockChainContract>>,
+    contracts: HashMap<HumanAddr, ContractInstance>,
+    balances: HashMap<HumanAddr, Coins>,
+    /// The storage of all contracts, each under the length prefixed contract address
+    storage: MemoryStorage,
+}
+
+impl ChainState {
+    fn code_of(&self, contract: &HumanAddr) -> StdResult<(Rc<dyn MockChainContract>, String)> {
+        let instance = self
+            .contracts
+            .get(contract)
+            .ok_or_else(|| StdError::not_found(format!("contract {}", contract)))?;
+        let code = self.codes[instance.code_id as usize - 1].clone();
+        Ok((code, code_hash(instance.code_id)))
+    }
+}
+
+/// The fake code hash of a stored code, it is only checked for consistency
+fn code_hash(code_id: u64) -> String {
+    format!("{:064x}", code_id)
+}
+
+fn contract_prefix(contract: &HumanAddr) -> Vec<u8> {
+    let mut prefix = (contract.len() as u16).to_be_bytes().to_vec();
+    prefix.extend_from_slice(contract.as_str().as_bytes());
+    prefix
+}
+
+/// ContractStorage is the storage of one contract on the MockChain
+pub struct ContractStorage {
+    state: Rc<RefCell<ChainState>>,
+    prefix: Vec<u8>,
+}
+
+impl ContractStorage {
+    fn prefixed(&self, key: &[u8]) -> Vec<u8> {
+        let mut prefixed = self.prefix.clone();
+        prefixed.extend_from_slice(key);
+        prefixed
+    }
+}
+
+impl ReadonlyStorage for ContractStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.state.borrow().storage.get(&self.prefixed(key))
+    }
+
+    #[cfg(feature = "iterator")]
+    /// The items are collected, as other contracts may write to the chain while iterating
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = KV> + 'a> {
+        let start = self.prefixed(start.unwrap_or_default());
+        let end = match end {
+            Some(end) => self.prefixed(end),
+            None => prefix_end(&self.prefix),
+        };
+        let items: Vec<KV> = self
+            .state
+            .borrow()
+            .storage
+            .range(Some(&start), Some(&end), order)
+            .map(|(key, value)| (key[self.prefix.len()..].to_vec(), value))
+            .collect();
+        Box::new(items.into_iter())
+    }
+}
+
+/// Returns the first key after all keys starting with the prefix. The prefix starts with its
+/// length, so it cannot consist of 0xff bytes only.
+#[cfg(feature = "iterator")]
+fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            break;
+        }
+    }
+    end
+}
+
+impl Storage for ContractStorage {
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        let key = self.prefixed(key);
+        self.state.borrow_mut().storage.set(&key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        let key = self.prefixed(key);
+        self.state.borrow_mut().storage.remove(&key);
+    }
+}
+
+/// ChainQuerier answers bank queries and queries to the contracts on the MockChain
+pub struct ChainQuerier {
+    state: Rc<RefCell<ChainState>>,
+    api: MockApi,
+}
+
+impl Querier for ChainQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        match request {
+            QueryRequest::Bank(BankQuery::Balance { address, denom }) => {
+                let amount = balance_of(&self.state.borrow(), &address).amount_of(&denom);
+                let res = BalanceResponse {
+                    amount: Coin { denom, amount },
+                };
+                Ok(to_binary(&res))
+            }
+            QueryRequest::Bank(BankQuery::AllBalances { address }) => {
+                let res = AllBalanceResponse {
+                    amount: balance_of(&self.state.borrow(), &address).to_vec(),
+                };
+                Ok(to_binary(&res))
+            }
+            QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr,
+                callback_code_hash,
+                msg,
+            }) => {
+                // the state must not be borrowed while the contract runs
+                let code = self.state.borrow().code_of(&contract_addr);
+                let (code, code_hash) = match code {
+                    Ok(code) => code,
+                    Err(_) => {
+                        return Err(SystemError::NoSuchContract {
+                            addr: contract_addr,
+                        })
+                    }
+                };
+                if let Err(e) = check_code_hash(&callback_code_hash, &code_hash) {
+                    return Ok(Err(e));
+                }
+                let deps = contract_deps(&self.state, self.api, &contract_addr);
+                let env = contract_env(&self.state, &contract_addr, &contract_addr, &[]);
+                Ok(code.query(&deps, env, msg.as_slice()))
+            }
+            QueryRequest::Wasm(WasmQuery::Raw {
+                contract_addr, key, ..
+            }) => {
+                if !self.state.borrow().contracts.contains_key(&contract_addr) {
+                    return Err(SystemError::NoSuchContract {
+                        addr: contract_addr,
+                    });
+                }
+                let storage = contract_deps(&self.state, self.api, &contract_addr).storage;
+                // a missing key reads as an empty value, like on chain
+                Ok(Ok(Binary(storage.get(key.as_slice()).unwrap_or_default())))
+            }
+            _ => Err(SystemError::UnsupportedRequest {
+                kind: "only bank and wasm queries are supported by MockChain".to_string(),
+            }),
+        }
+    }
+}
+
+fn balance_of(state: &ChainState, address: &HumanAddr) -> Coins {
+    state.balances.get(address).cloned().unwrap_or_default()
+}
+
+fn check_code_hash(callback_code_hash: &str, code_hash: &str) -> StdResult<()> {
+    if callback_code_hash != code_hash {
+        return Err(StdError::generic_err(format!(
+            "Invalid callback code hash {}, the contract has code hash {}",
+            callback_code_hash, code_hash
+        )));
+    }
+    Ok(())
+}
+
+fn contract_deps(state: &Rc<RefCell<ChainState>>, api: MockApi, contract: &HumanAddr) -> ChainDeps {
+    Extern {
+        storage: ContractStorage {
+            state: state.clone(),
+            prefix: contract_prefix(contract),
+        },
+        api,
+        querier: ChainQuerier {
+            state: state.clone(),
+            api,
+        },
+    }
+}
+
+fn contract_env(
+    state: &Rc<RefCell<ChainState>>,
+    contract: &HumanAddr,
+    sender: &HumanAddr,
+    sent: &[Coin],
+) -> Env {
+    let state = state.borrow();
+    let code_id = state.contracts[contract].code_id;
+    Env {
+        block: state.block.clone(),
+        message: MessageInfo {
+            sender: sender.clone(),
+            sent_funds: sent.to_vec(),
+        },
+        contract: ContractInfo {
+            address: contract.clone(),
+        },
+        contract_key: Some("".to_string()),
+        contract_code_hash: code_hash(code_id),
+    }
+}
+
+/// MockChain simulates a chain with a bank module and the contracts stored on it.
+///
+/// Every call to `instantiate`, `execute` and `send` is a transaction: the messages returned by
+/// the contracts are executed in order, and if any of them fails, all changes to balances and
+/// contract storage are rolled back.
+pub struct MockChain {
+    api: MockApi,
+    state: Rc<RefCell<ChainState>>,
+}
+
+impl Default for MockChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockChain {
+    /// Starts at the block of mock_env, addresses are converted with `MockApi::new(20)`
+    pub fn new() -> Self {
+        MockChain {
+            api: MockApi::new(20),
+            state: Rc::new(RefCell::new(ChainState {
+                block: mock_env("", &[]).block,
+                codes: vec![],
+                contracts: HashMap::new(),
+                balances: HashMap::new(),
+                storage: MemoryStorage::new(),
+            })),
+        }
+    }
+
+    /// Stores the code of a contract and returns its code id
+    pub fn store_code<C: MockChainContract + 'static>(&mut self, contract: C) -> u64 {
+        let mut state = self.state.borrow_mut();
+        state.codes.push(Rc::new(contract));
+        state.codes.len() as u64
+    }
+
+    /// Returns the code hash that callbacks to contracts of this code must use
+    pub fn code_hash(&self, code_id: u64) -> String {
+        code_hash(code_id)
+    }
+
+    pub fn block(&self) -> BlockInfo {
+        self.state.borrow().block.clone()
+    }
+
+    /// Moves to a later block, MOCK_BLOCK_TIME seconds per block
+    pub fn advance_blocks(&mut self, blocks: u64) {
+        let mut state = self.state.borrow_mut();
+        state.block.height += blocks;
+        state.block.time += blocks * MOCK_BLOCK_TIME;
+    }
+
+    /// Moves the block time forward, without changing the height
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.state.borrow_mut().block.time += seconds;
+    }
+
+    /// Sets the balance of the given address, replacing a previous one
+    pub fn set_balance<U: Into<HumanAddr>>(&mut self, addr: U, balance: &[Coin]) -> StdResult<()> {
+        let balance = Coins::try_from(balance)?;
+        self.state
+            .borrow_mut()
+            .balances
+            .insert(addr.into(), balance);
+        Ok(())
+    }
+
+    /// Returns the balance of the given address, sorted by denom
+    pub fn balance<U: Into<HumanAddr>>(&self, addr: U) -> Vec<Coin> {
+        balance_of(&self.state.borrow(), &addr.into()).to_vec()
+    }
+
+    /// Returns the label and code id of a contract
+    pub fn contract_info<U: Into<HumanAddr>>(&self, contract: U) -> Option<(String, u64)> {
+        let state = self.state.borrow();
+        state
+            .contracts
+            .get(&contract.into())
+            .map(|instance| (instance.label.clone(), instance.code_id))
+    }
+
+    /// Instantiates a contract of the stored code and returns its address
+    pub fn instantiate<U: Into<HumanAddr>, M: Serialize>(
+        &mut self,
+        code_id: u64,
+        sender: U,
+        msg: &M,
+        send: &[Coin],
+        label: &str,
+    ) -> StdResult<(HumanAddr, ChainResponse)> {
+        let msg = WasmMsg::Instantiate {
+            code_id,
+            callback_code_hash: code_hash(code_id),
+            msg: to_vec(msg)?.into(),
+            send: send.to_vec(),
+            label: label.to_string(),
+        };
+        let next_address = self.next_address();
+        let res = self.send(sender, msg)?;
+        Ok((next_address, res))
+    }
+
+    /// Executes a contract with the given message and funds
+    pub fn execute<U: Into<HumanAddr>, V: Into<HumanAddr>, M: Serialize>(
+        &mut self,
+        sender: U,
+        contract_addr: V,
+        msg: &M,
+        send: &[Coin],
+    ) -> StdResult<ChainResponse> {
+        let contract_addr = contract_addr.into();
+        let (_, code_hash) = self.state.borrow().code_of(&contract_addr)?;
+        let msg = WasmMsg::Execute {
+            contract_addr,
+            callback_code_hash: code_hash,
+            msg: to_vec(msg)?.into(),
+            send: send.to_vec(),
+        };
+        self.send(sender, msg)
+    }
+
+    /// Executes any message as the given sender, e.g. a bank transfer
+    pub fn send<U: Into<HumanAddr>, M: Into<CosmosMsg>>(
+        &mut self,
+        sender: U,
+        msg: M,
+    ) -> StdResult<ChainResponse> {
+        let snapshot = self.state.borrow().clone();
+        let res = self.dispatch(&sender.into(), msg.into());
+        if res.is_err() {
+            *self.state.borrow_mut() = snapshot;
+        }
+        res
+    }
+
+    /// Queries a contract, the response is parsed into whatever type the caller expects
+    pub fn query<T: DeserializeOwned, U: Into<HumanAddr>, M: Serialize>(
+        &self,
+        contract_addr: U,
+        msg: &M,
+    ) -> StdResult<T> {
+        let contract_addr = contract_addr.into();
+        let (_, code_hash) = self.state.borrow().code_of(&contract_addr)?;
+        self.querier()
+            .query_wasm_smart(contract_addr, &code_hash, msg)
+    }
+
+    /// Returns a querier of the chain state, as contracts see it
+    pub fn querier(&self) -> ChainQuerier {
+        ChainQuerier {
+            state: self.state.clone(),
+            api: self.api,
+        }
+    }
+
+    fn next_address(&self) -> HumanAddr {
+        HumanAddr(format!(
+            "contract{}",
+            self.state.borrow().contracts.len() + 1
+        ))
+    }
+
+    fn dispatch(&self, sender: &HumanAddr, msg: CosmosMsg) -> StdResult<ChainResponse> {
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send {
+                from_address,
+                to_address,
+                amount,
+            }) => {
+                if &from_address != sender {
+                    return Err(StdError::generic_err(format!(
+                        "{} cannot send the funds of {}",
+                        sender, from_address
+                    )));
+                }
+                let event = self.transfer(sender, &to_address, &amount)?;
+                Ok(ChainResponse {
+                    events: event.into_iter().collect(),
+                    data: None,
+                })
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                callback_code_hash,
+                msg,
+                send,
+            }) => {
+                let (code, code_hash) = self.state.borrow().code_of(&contract_addr)?;
+                check_code_hash(&callback_code_hash, &code_hash)?;
+                let mut events: Vec<Event> = self
+                    .transfer(sender, &contract_addr, &send)?
+                    .into_iter()
+                    .collect();
+
+                let mut deps = contract_deps(&self.state, self.api, &contract_addr);
+                let env = contract_env(&self.state, &contract_addr, sender, &send);
+                let res = code.handle(&mut deps, env, msg.as_slice())?;
+                let res = self.process_response(&contract_addr, res)?;
+                events.extend(res.events);
+                Ok(ChainResponse {
+                    events,
+                    data: res.data,
+                })
+            }
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id,
+                callback_code_hash,
+                msg,
+                send,
+                label,
+            }) => {
+                let code = {
+                    let state = self.state.borrow();
+                    if code_id == 0 || code_id as usize > state.codes.len() {
+                        return Err(StdError::not_found(format!("code {}", code_id)));
+                    }
+                    state.codes[code_id as usize - 1].clone()
+                };
+                check_code_hash(&callback_code_hash, &code_hash(code_id))?;
+                let contract_addr = self.next_address();
+                self.state
+                    .borrow_mut()
+                    .contracts
+                    .insert(contract_addr.clone(), ContractInstance { code_id, label });
+                let mut events: Vec<Event> = self
+                    .transfer(sender, &contract_addr, &send)?
+                    .into_iter()
+                    .collect();
+
+                let mut deps = contract_deps(&self.state, self.api, &contract_addr);
+                let env = contract_env(&self.state, &contract_addr, sender, &send);
+                let res = code.init(&mut deps, env, msg.as_slice())?;
+                let res = self.process_response(&contract_addr, res)?;
+                events.extend(res.events);
+                Ok(ChainResponse {
+                    events,
+                    data: res.data,
+                })
+            }
+            _ => Err(StdError::generic_err(
+                "only bank and wasm messages are supported by MockChain",
+            )),
+        }
+    }
+
+    /// Moves the funds between the accounts, and returns the event of the transfer if any
+    fn transfer(
+        &self,
+        from: &HumanAddr,
+        to: &HumanAddr,
+        amount: &[Coin],
+    ) -> StdResult<Option<Event>> {
+        let amount = Coins::try_from(amount)?;
+        if amount.is_empty() {
+            return Ok(None);
+        }
+        let mut state = self.state.borrow_mut();
+        let from_balance = balance_of(&state, from).checked_sub(&amount).map_err(|_| {
+            StdError::generic_err(format!(
+                "Insufficient funds: {} cannot send {}",
+                from, amount
+            ))
+        })?;
+        state.balances.insert(from.clone(), from_balance);
+        let to_balance = balance_of(&state, to).checked_add(&amount)?;
+        state.balances.insert(to.clone(), to_balance);
+
+        Ok(Some(Event {
+            ty: "transfer".to_string(),
+            attributes: vec![
+                plaintext_log("recipient", to),
+                plaintext_log("sender", from),
+                plaintext_log("amount", &amount),
+            ],
+        }))
+    }
+
+    /// Executes the messages and submessages of a contract response and collects the events,
+    /// the contract log becomes a "wasm" event
+    fn process_response(
+        &self,
+        contract: &HumanAddr,
+        response: HandleResponse,
+    ) -> StdResult<ChainResponse> {
+        let mut events = vec![];
+        if !response.log.is_empty() {
+            let mut attributes = vec![plaintext_log("_contract_address", contract)];
+            attributes.extend(response.log);
+            events.push(Event {
+                ty: "wasm".to_string(),
+                attributes,
+            });
+        }
+        for mut event in response.events {
+            event.ty = format!("wasm-{}", event.ty);
+            event
+                .attributes
+                .insert(0, plaintext_log("_contract_address", contract));
+            events.push(event);
+        }
+
+        for msg in response.messages {
+            events.extend(self.dispatch(contract, msg)?.events);
+        }
+
+        let mut data = response.data;
+        for submsg in response.submessages {
+            let snapshot = self.state.borrow().clone();
+            let result = match self.dispatch(contract, submsg.msg) {
+                Ok(res) => {
+                    match submsg.reply_on {
+                        ReplyOn::Always | ReplyOn::Success => {}
+                        ReplyOn::Error | ReplyOn::Never => {
+                            events.extend(res.events);
+                            continue;
+                        }
+                    }
+                    events.extend(res.events.iter().cloned());
+                    SubMsgResult::Ok(SubMsgExecutionResponse {
+                        log: vec![],
+                        events: res.events,
+                        data: res.data,
+                    })
+                }
+                Err(err) => {
+                    // the changes of the failed submessage are dropped, the contract handles the error
+                    *self.state.borrow_mut() = snapshot;
+                    match submsg.reply_on {
+                        ReplyOn::Always | ReplyOn::Error => SubMsgResult::Err(err.to_string()),
+                        ReplyOn::Success | ReplyOn::Never => return Err(err),
+                    }
+                }
+            };
+
+            let (code, _) = self.state.borrow().code_of(contract)?;
+            let mut deps = contract_deps(&self.state, self.api, contract);
+            let env = contract_env(&self.state, contract, contract, &[]);
+            let reply = Reply {
+                id: submsg.id,
+                result,
+            };
+            let res = self.process_response(contract, code.reply(&mut deps, env, reply)?)?;
+            events.extend(res.events);
+            if res.data.is_some() {
+                data = res.data;
+            }
+        }
+
+        Ok(ChainResponse { events, data })
+    }
+}
+
+impl ChainQuerier {
+    /// Returns the balance of the given address, as the bank module would
+    pub fn balance<U: Into<HumanAddr>>(&self, addr: U) -> Vec<Coin> {
+        balance_of(&self.state.borrow(), &addr.into()).to_vec()
+    }
+}
+
+/// Parses the data a contract set in its response
+pub fn parse_data<T: DeserializeOwned>(response: &ChainResponse) -> StdResult<T> {
+    match &response.data {
+        Some(data) => from_binary(data),
+        None => Err(StdError::not_found("response data")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coins::coins;
+    use crate::init_handle::log;
+    use crate::submessages::SubMsg;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    struct InitMsg {
+        count: u64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum HandleMsg {
+        Increment {},
+        /// Forwards the sent funds to the recipient
+        Forward {
+            recipient: HumanAddr,
+        },
+        /// Increments another counter with a message
+        IncrementOther {
+            contract: HumanAddr,
+            code_hash: String,
+        },
+        /// Increments another counter with a submessage, replying on error
+        TryIncrementOther {
+            contract: HumanAddr,
+            code_hash: String,
+        },
+        /// Copies the count of another counter, read with a query
+        CopyOther {
+            contract: HumanAddr,
+            code_hash: String,
+        },
+        Fail {},
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum QueryMsg {
+        Count {},
+    }
+
+    fn load_count(deps: &ChainDeps) -> u64 {
+        deps.storage
+            .get(b"count")
+            .map_or(0, |v| from_slice(&v).unwrap())
+    }
+
+    fn init(deps: &mut ChainDeps, _env: Env, msg: InitMsg) -> StdResult<HandleResponse> {
+        deps.storage.set(b"count", &to_vec(&msg.count)?);
+        Ok(HandleResponse::default())
+    }
+
+    fn handle(deps: &mut ChainDeps, env: Env, msg: HandleMsg) -> StdResult<HandleResponse> {
+        let mut res = HandleResponse::default();
+        match msg {
+            HandleMsg::Increment {} => {
+                let count = load_count(deps) + 1;
+                deps.storage.set(b"count", &to_vec(&count)?);
+                res.log.push(log("count", count));
+                res.data = Some(to_binary(&count)?);
+            }
+            HandleMsg::Forward { recipient } => {
+                res.messages.push(
+                    BankMsg::Send {
+                        from_address: env.contract.address,
+                        to_address: recipient,
+                        amount: env.message.sent_funds,
+                    }
+                    .into(),
+                );
+            }
+            HandleMsg::IncrementOther {
+                contract,
+                code_hash,
+            } => {
+                res.messages.push(increment_msg(contract, code_hash)?);
+            }
+            HandleMsg::TryIncrementOther {
+                contract,
+                code_hash,
+            } => {
+                let msg = increment_msg(contract, code_hash)?;
+                res.submessages.push(SubMsg::reply_on_error(msg, 7));
+            }
+            HandleMsg::CopyOther {
+                contract,
+                code_hash,
+            } => {
+                let count: u64 =
+                    deps.querier
+                        .query_wasm_smart(contract, &code_hash, &QueryMsg::Count {})?;
+                deps.storage.set(b"count", &to_vec(&count)?);
+            }
+            HandleMsg::Fail {} => return Err(StdError::generic_err("failed")),
+        }
+        Ok(res)
+    }
+
+    fn increment_msg(contract: HumanAddr, code_hash: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract,
+            callback_code_hash: code_hash,
+            msg: to_binary(&HandleMsg::Increment {})?,
+            send: vec![],
+        }
+        .into())
+    }
+
+    fn query(deps: &ChainDeps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Count {} => to_binary(&load_count(deps)),
+        }
+    }
+
+    fn reply(deps: &mut ChainDeps, _env: Env, msg: Reply) -> StdResult<HandleResponse> {
+        // counts the failed submessages
+        assert!(msg.result.is_err());
+        deps.storage.set(b"count", &to_vec(&(1000 + msg.id))?);
+        Ok(HandleResponse::default())
+    }
+
+    fn setup() -> (MockChain, String, HumanAddr, HumanAddr) {
+        let mut chain = MockChain::new();
+        let code_id = chain.store_code(ContractWrapper::new(init, handle, query).with_reply(reply));
+        let (first, _) = chain
+            .instantiate(code_id, "creator", &InitMsg { count: 1 }, &[], "first")
+            .unwrap();
+        let (second, _) = chain
+            .instantiate(code_id, "creator", &InitMsg { count: 10 }, &[], "second")
+            .unwrap();
+        let code_hash = chain.code_hash(code_id);
+        (chain, code_hash, first, second)
+    }
+
+    #[test]
+    fn contracts_have_separate_storage() {
+        let (mut chain, code_hash, first, second) = setup();
+        assert_eq!(chain.contract_info(&first), Some(("first".to_string(), 1)));
+
+        let res = chain
+            .execute("user", &first, &HandleMsg::Increment {}, &[])
+            .unwrap();
+        assert_eq!(parse_data::<u64>(&res).unwrap(), 2);
+        let event = res.contract_event("wasm", &first).unwrap();
+        assert_eq!(event.attributes[1], log("count", 2));
+
+        let count: u64 = chain.query(&first, &QueryMsg::Count {}).unwrap();
+        assert_eq!(count, 2);
+        let count: u64 = chain.query(&second, &QueryMsg::Count {}).unwrap();
+        assert_eq!(count, 10);
+
+        let querier = chain.querier();
+        let raw = querier.query_wasm_raw(&second, &code_hash, b"count");
+        assert_eq!(raw, Ok(Some(to_vec(&10u64).unwrap())));
+        assert_eq!(
+            querier.query_wasm_raw(&second, &code_hash, b"missing"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn bank_transfers_work() {
+        let (mut chain, _, first, _) = setup();
+        chain.set_balance("user", &coins(100, "uscrt")).unwrap();
+
+        let msg = HandleMsg::Forward {
+            recipient: HumanAddr::from("friend"),
+        };
+        let res = chain
+            .execute("user", &first, &msg, &coins(30, "uscrt"))
+            .unwrap();
+        assert_eq!(res.events.len(), 2);
+        assert_eq!(chain.balance("user"), coins(70, "uscrt"));
+        assert_eq!(chain.balance(&first), vec![]);
+        assert_eq!(chain.balance("friend"), coins(30, "uscrt"));
+
+        let send = BankMsg::Send {
+            from_address: HumanAddr::from("friend"),
+            to_address: HumanAddr::from("user"),
+            amount: coins(10, "uscrt"),
+        };
+        chain.send("friend", send.clone()).unwrap();
+        assert_eq!(chain.balance("friend"), coins(20, "uscrt"));
+        // only the owner can send its funds
+        assert!(chain.send("user", send).is_err());
+
+        let err = chain
+            .execute("user", &first, &msg, &coins(81, "uscrt"))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Insufficient funds: user cannot send 81uscrt")
+        );
+    }
+
+    #[test]
+    fn contracts_call_and_query_each_other() {
+        let (mut chain, code_hash, first, second) = setup();
+
+        let msg = HandleMsg::IncrementOther {
+            contract: second.clone(),
+            code_hash: code_hash.clone(),
+        };
+        chain.execute("user", &first, &msg, &[]).unwrap();
+        let count: u64 = chain.query(&second, &QueryMsg::Count {}).unwrap();
+        assert_eq!(count, 11);
+
+        let msg = HandleMsg::CopyOther {
+            contract: second.clone(),
+            code_hash: code_hash.clone(),
+        };
+        chain.execute("user", &first, &msg, &[]).unwrap();
+        let count: u64 = chain.query(&first, &QueryMsg::Count {}).unwrap();
+        assert_eq!(count, 11);
+
+        let msg = HandleMsg::IncrementOther {
+            contract: second,
+            code_hash: "wrong".to_string(),
+        };
+        assert!(chain.execute("user", &first, &msg, &[]).is_err());
+    }
+
+    #[test]
+    fn failed_transactions_are_rolled_back() {
+        let (mut chain, _, first, _) = setup();
+        chain.set_balance("user", &coins(100, "uscrt")).unwrap();
+
+        // the second counter does not exist, so the increment of the first is dropped
+        let msg = HandleMsg::IncrementOther {
+            contract: HumanAddr::from("contract9"),
+            code_hash: chain.code_hash(1),
+        };
+        assert!(chain
+            .execute("user", &first, &msg, &coins(10, "uscrt"))
+            .is_err());
+        assert_eq!(chain.balance("user"), coins(100, "uscrt"));
+
+        chain
+            .execute("user", &first, &HandleMsg::Fail {}, &coins(10, "uscrt"))
+            .unwrap_err();
+        assert_eq!(chain.balance("user"), coins(100, "uscrt"));
+        assert_eq!(chain.balance(&first), vec![]);
+    }
+
+    #[test]
+    fn submessage_errors_are_replied() {
+        let (mut chain, code_hash, first, _) = setup();
+
+        let msg = HandleMsg::TryIncrementOther {
+            contract: HumanAddr::from("contract9"),
+            code_hash,
+        };
+        chain.execute("user", &first, &msg, &[]).unwrap();
+        let count: u64 = chain.query(&first, &QueryMsg::Count {}).unwrap();
+        assert_eq!(count, 1007);
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn contract_storage_ranges_stay_in_prefix() {
+        let (chain, _, first, second) = setup();
+        let mut first = contract_deps(&chain.state, chain.api, &first).storage;
+        let mut second = contract_deps(&chain.state, chain.api, &second).storage;
+        first.set(b"a", b"1");
+        first.set(b"b", b"2");
+        second.set(b"a", b"3");
+
+        let items: Vec<KV> = first.range(None, None, Order::Ascending).collect();
+        assert_eq!(
+            items,
+            vec![
+                (b"a".to_vec(), b"1".to_vec()),
+                (b"b".to_vec(), b"2".to_vec()),
+                (b"count".to_vec(), to_vec(&1u64).unwrap()),
+            ]
+        );
+        let items: Vec<KV> = second.range(Some(b"b"), None, Order::Descending).collect();
+        assert_eq!(items, vec![(b"count".to_vec(), to_vec(&10u64).unwrap())]);
+        let items: Vec<KV> = first
+            .range(Some(b"a"), Some(b"b"), Order::Ascending)
+            .collect();
+        assert_eq!(items, vec![(b"a".to_vec(), b"1".to_vec())]);
+    }
+
+    #[test]
+    fn blocks_advance() {
+        let mut chain = MockChain::new();
+        let block = chain.block();
+        chain.advance_blocks(2);
+        assert_eq!(chain.block().height, block.height + 2);
+        assert_eq!(chain.block().time, block.time + 2 * MOCK_BLOCK_TIME);
+        chain.advance_time(100);
+        assert_eq!(chain.block().height, block.height + 2);
+        assert_eq!(chain.block().time, block.time + 2 * MOCK_BLOCK_TIME + 100);
+    }
+}
@@ -8,7 +8,7 @@ use std::ops::{Bound, RangeBounds};
 use crate::iterator::{Order, KV};
 use crate::traits::{ReadonlyStorage, Storage};
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct MemoryStorage {
     data: BTreeMap<Vec<u8>, Vec<u8>>,
 }
@@ -17,6 +17,14 @@ impl MemoryStorage {
     pub fn new() -> Self {
         MemoryStorage::default()
     }
+
+    /// Returns all entries in key order. Unlike `range`, this does not need the iterator
+    /// feature, so test tooling can always inspect the whole storage.
+    pub fn entries(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.data
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+    }
 }
 
 impl ReadonlyStorage for MemoryStorage {
@@ -105,6 +113,18 @@ mod test {
         assert_eq!(store.get(b"food"), Some(b"bank".to_vec()));
     }
 
+    #[test]
+    fn entries_are_sorted_by_key() {
+        let mut store = MemoryStorage::new();
+        store.set(b"food", b"bank");
+        store.set(b"foo", b"bar");
+        let entries: Vec<_> = store.entries().collect();
+        assert_eq!(
+            entries,
+            vec![(&b"foo"[..], &b"bar"[..]), (&b"food"[..], &b"bank"[..])]
+        );
+    }
+
     #[test]
     #[cfg(feature = "iterator")]
     fn iterator() {
@@ -0,0 +1,177 @@
+//! Types for submessages and the reply entry point
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::encoding::Binary;
+use crate::init_handle::{CosmosMsg, Event, LogAttribute};
+use crate::types::Empty;
+
+/// ReplyOn tells the runtime when the result of a submessage must be sent back to the
+/// `reply` entry point of the calling contract.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReplyOn {
+    /// Always call back, whether the submessage succeeded or failed
+    Always,
+    /// Only call back if the submessage failed, a success is applied like a plain message
+    Error,
+    /// Only call back if the submessage succeeded, a failure aborts the whole transaction
+    Success,
+    /// Never call back, this behaves exactly like a message in `HandleResponse.messages`
+    #[default]
+    Never,
+}
+
+/// SubMsg wraps a CosmosMsg with an id and a reply policy, so the contract learns
+/// about the result of the call in its `reply` entry point.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SubMsg<T = Empty>
+where
+    T: Clone + fmt::Debug + PartialEq,
+{
+    /// id is chosen by the contract and handed back in `Reply.id`, to match replies with submessages
+    pub id: u64,
+    pub msg: CosmosMsg<T>,
+    pub reply_on: ReplyOn,
+}
+
+impl<T> SubMsg<T>
+where
+    T: Clone + fmt::Debug + PartialEq,
+{
+    /// Creates a submessage which never calls back
+    pub fn new<M: Into<CosmosMsg<T>>>(msg: M) -> Self {
+        SubMsg {
+            id: 0,
+            msg: msg.into(),
+            reply_on: ReplyOn::Never,
+        }
+    }
+
+    pub fn reply_always<M: Into<CosmosMsg<T>>>(msg: M, id: u64) -> Self {
+        SubMsg {
+            id,
+            msg: msg.into(),
+            reply_on: ReplyOn::Always,
+        }
+    }
+
+    pub fn reply_on_success<M: Into<CosmosMsg<T>>>(msg: M, id: u64) -> Self {
+        SubMsg {
+            id,
+            msg: msg.into(),
+            reply_on: ReplyOn::Success,
+        }
+    }
+
+    pub fn reply_on_error<M: Into<CosmosMsg<T>>>(msg: M, id: u64) -> Self {
+        SubMsg {
+            id,
+            msg: msg.into(),
+            reply_on: ReplyOn::Error,
+        }
+    }
+}
+
+/// Reply is the message sent to the `reply` entry point once a submessage was executed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Reply {
+    /// The id of the SubMsg that triggered this reply
+    pub id: u64,
+    pub result: SubMsgResult,
+}
+
+/// SubMsgResult is the outcome of a submessage. Errors are passed as strings, as they
+/// can originate from any module of the chain and not only from contracts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SubMsgResult {
+    Ok(SubMsgExecutionResponse),
+    Err(String),
+}
+
+impl SubMsgResult {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, SubMsgResult::Ok(_))
+    }
+
+    pub fn is_err(&self) -> bool {
+        matches!(self, SubMsgResult::Err(_))
+    }
+
+    /// Converts the result into a standard Result, for use with `?`
+    pub fn into_result(self) -> Result<SubMsgExecutionResponse, String> {
+        match self {
+            SubMsgResult::Ok(response) => Ok(response),
+            SubMsgResult::Err(err) => Err(err),
+        }
+    }
+}
+
+/// SubMsgExecutionResponse holds the log and data returned by a successful submessage,
+/// e.g. the address of a contract created with `WasmMsg::Instantiate`.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SubMsgExecutionResponse {
+    pub log: Vec<LogAttribute>,
+    /// The events emitted by the submessage, kept apart from those of the calling contract
+    #[serde(default)]
+    pub events: Vec<Event>,
+    pub data: Option<Binary>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::addresses::HumanAddr;
+    use crate::coins::coins;
+    use crate::init_handle::BankMsg;
+    use crate::serde::{from_slice, to_vec};
+
+    #[test]
+    fn sub_msg_constructors_set_reply_on() {
+        let bank = BankMsg::Send {
+            from_address: HumanAddr::from("me"),
+            to_address: HumanAddr::from("you"),
+            amount: coins(1015, "earth"),
+        };
+
+        let msg: SubMsg = SubMsg::new(bank.clone());
+        assert_eq!(msg.id, 0);
+        assert_eq!(msg.reply_on, ReplyOn::Never);
+
+        let msg: SubMsg = SubMsg::reply_always(bank.clone(), 1);
+        assert_eq!((msg.id, msg.reply_on), (1, ReplyOn::Always));
+        let msg: SubMsg = SubMsg::reply_on_success(bank.clone(), 2);
+        assert_eq!((msg.id, msg.reply_on), (2, ReplyOn::Success));
+        let msg: SubMsg = SubMsg::reply_on_error(bank.clone(), 3);
+        assert_eq!((msg.id, msg.reply_on), (3, ReplyOn::Error));
+        assert_eq!(msg.msg, CosmosMsg::Bank(bank));
+    }
+
+    #[test]
+    fn reply_serializes_as_snake_case() {
+        let reply = Reply {
+            id: 7,
+            result: SubMsgResult::Ok(SubMsgExecutionResponse {
+                log: vec![],
+                events: vec![],
+                data: Some(Binary::from(b"addr")),
+            }),
+        };
+        let serialized = to_vec(&reply).unwrap();
+        assert_eq!(
+            serialized,
+            br#"{"id":7,"result":{"ok":{"log":[],"events":[],"data":"YWRkcg=="}}}"#.to_vec()
+        );
+
+        let failed: Reply = from_slice(br#"{"id":7,"result":{"err":"out of gas"}}"#).unwrap();
+        assert!(failed.result.is_err());
+        assert_eq!(failed.result.into_result(), Err("out of gas".to_string()));
+    }
+}
@@ -0,0 +1,45 @@
+//! Types for the privileged sudo entry point
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::types::Empty;
+
+/// SudoMsg is sent to the `sudo` export by the Omnibus runtime itself. It never originates
+/// from a user transaction, so contracts can trust it without checking the sender.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg<T = Empty>
+where
+    T: Clone + fmt::Debug + PartialEq,
+{
+    /// Sent at the end of each block to contracts that take part in end-of-block processing
+    EndBlock {},
+    /// A governance proposal changed one of the parameters of this contract
+    ParamChange { key: String, value: String },
+    /// Runtime specific actions, the contract defines the format
+    Custom(T),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::serde::{from_slice, to_vec};
+
+    #[test]
+    fn sudo_msg_serializes_as_snake_case() {
+        let msg: SudoMsg = SudoMsg::ParamChange {
+            key: "fee".to_string(),
+            value: "12".to_string(),
+        };
+        let serialized = to_vec(&msg).unwrap();
+        assert_eq!(
+            serialized,
+            br#"{"param_change":{"key":"fee","value":"12"}}"#.to_vec()
+        );
+
+        let deserialized: SudoMsg = from_slice(br#"{"end_block":{}}"#).unwrap();
+        assert_eq!(deserialized, SudoMsg::EndBlock {});
+    }
+}
@@ -0,0 +1,167 @@
+use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// A point in time in nanosecond precision, counted from the beginning of the UNIX epoch
+/// (Jan. 1, 1970 00:00:00 UTC).
+///
+/// It is serialized as a string of nanoseconds, as the JSON numbers of most clients cannot
+/// represent such values without loss of precision.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Timestamp(nanos)
+    }
+
+    /// Panics if the nanoseconds do not fit into a u64, which is the case after the year 2554
+    pub const fn from_seconds(seconds: u64) -> Self {
+        match seconds.checked_mul(1_000_000_000) {
+            Some(nanos) => Timestamp(nanos),
+            None => panic!("attempt to multiply with overflow"),
+        }
+    }
+
+    pub const fn nanos(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the whole seconds, the nanoseconds of the last started second are dropped
+    pub const fn seconds(&self) -> u64 {
+        self.0 / 1_000_000_000
+    }
+
+    /// Returns the nanoseconds of the last started second
+    pub const fn subsec_nanos(&self) -> u64 {
+        self.0 % 1_000_000_000
+    }
+
+    pub fn checked_add_nanos(&self, nanos: u64) -> Option<Timestamp> {
+        self.0.checked_add(nanos).map(Timestamp)
+    }
+
+    pub fn checked_sub_nanos(&self, nanos: u64) -> Option<Timestamp> {
+        self.0.checked_sub(nanos).map(Timestamp)
+    }
+
+    /// Panics on overflow
+    pub fn plus_nanos(&self, nanos: u64) -> Timestamp {
+        self.checked_add_nanos(nanos)
+            .expect("attempt to add with overflow")
+    }
+
+    /// Panics on overflow
+    pub fn plus_seconds(&self, seconds: u64) -> Timestamp {
+        self.plus_nanos(
+            seconds
+                .checked_mul(1_000_000_000)
+                .expect("attempt to multiply with overflow"),
+        )
+    }
+
+    /// Panics if the result would be before the UNIX epoch
+    pub fn minus_nanos(&self, nanos: u64) -> Timestamp {
+        self.checked_sub_nanos(nanos)
+            .expect("attempt to subtract with overflow")
+    }
+
+    /// Panics if the result would be before the UNIX epoch
+    pub fn minus_seconds(&self, seconds: u64) -> Timestamp {
+        self.minus_nanos(
+            seconds
+                .checked_mul(1_000_000_000)
+                .expect("attempt to multiply with overflow"),
+        )
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:09}", self.seconds(), self.subsec_nanos())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TimestampVisitor)
+    }
+}
+
+struct TimestampVisitor;
+
+impl<'de> de::Visitor<'de> for TimestampVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string-encoded nanoseconds")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse::<u64>()
+            .map(Timestamp)
+            .map_err(|e| E::custom(format!("invalid Timestamp '{}' - {}", v, e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::serde::{from_slice, to_vec};
+
+    #[test]
+    fn timestamp_conversions_work() {
+        let time = Timestamp::from_seconds(1_571_797_419).plus_nanos(879_305_533);
+        assert_eq!(time.nanos(), 1_571_797_419_879_305_533);
+        assert_eq!(time.seconds(), 1_571_797_419);
+        assert_eq!(time.subsec_nanos(), 879_305_533);
+        assert_eq!(time.to_string(), "1571797419.879305533");
+        assert_eq!(Timestamp::from_nanos(5).to_string(), "0.000000005");
+    }
+
+    #[test]
+    fn timestamp_arithmetic_works() {
+        let time = Timestamp::from_seconds(100);
+        assert_eq!(time.plus_seconds(20), Timestamp::from_seconds(120));
+        assert_eq!(time.minus_seconds(20), Timestamp::from_seconds(80));
+        assert_eq!(time.minus_nanos(1).nanos(), 99_999_999_999);
+        assert_eq!(time.checked_sub_nanos(100_000_000_001), None);
+        assert_eq!(Timestamp::from_nanos(u64::MAX).checked_add_nanos(1), None);
+        assert!(time.plus_nanos(1) > time);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn timestamp_minus_panics_before_epoch() {
+        Timestamp::from_seconds(1).minus_seconds(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn timestamp_from_seconds_panics_on_overflow() {
+        Timestamp::from_seconds(u64::MAX / 1_000_000_000 + 1);
+    }
+
+    #[test]
+    fn timestamp_serializes_as_string() {
+        let time = Timestamp::from_nanos(1_571_797_419_879_305_533);
+        let serialized = to_vec(&time).unwrap();
+        assert_eq!(serialized, br#""1571797419879305533""#.to_vec());
+        assert_eq!(from_slice::<Timestamp>(&serialized).unwrap(), time);
+        assert!(from_slice::<Timestamp>(br#""-1""#).is_err());
+    }
+}
@@ -1,16 +1,20 @@
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::addresses::{CanonicalAddr, HumanAddr};
+use crate::addresses::{Addr, CanonicalAddr, HumanAddr};
 use crate::coins::Coin;
 use crate::encoding::Binary;
 use crate::errors::{StdError, StdResult, SystemResult};
 #[cfg(feature = "iterator")]
 use crate::iterator::{Order, KV};
-use crate::query::{AllBalanceResponse, BalanceResponse, BankQuery, QueryRequest};
+use crate::query::{
+    AllBalanceResponse, BalanceResponse, BankQuery, BondedRatioResponse, DistQuery, GovQuery,
+    InflationResponse, MintQuery, Proposal, ProposalsResponse, QueryRequest, RewardsResponse,
+    WasmQuery,
+};
 #[cfg(feature = "staking")]
 use crate::query::{
     AllDelegationsResponse, BondedDenomResponse, Delegation, DelegationResponse, FullDelegation,
-    StakingQuery, Validator, ValidatorsResponse,
+    StakingQuery, UnbondingDelegationsResponse, Validator, ValidatorsResponse,
 };
 use crate::serde::{from_binary, to_vec};
 use crate::types::Empty;
@@ -83,6 +87,24 @@ pub trait Storage: ReadonlyStorage {
 pub trait Api: Copy + Clone + Send {
     fn canonical_address(&self, human: &HumanAddr) -> StdResult<CanonicalAddr>;
     fn human_address(&self, canonical: &CanonicalAddr) -> StdResult<HumanAddr>;
+
+    /// Checks the format of a human address and that it is normalized, i.e. it round-trips
+    /// through `canonical_address` and `human_address` unchanged. This is the only way for
+    /// contract code to build an `Addr`. Implementations overriding it build the result with
+    /// `Addr::unchecked`.
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        if human.is_empty() {
+            return Err(StdError::generic_err("Invalid input: empty address"));
+        }
+        let canonical = self.canonical_address(&HumanAddr::from(human))?;
+        let normalized = self.human_address(&canonical)?;
+        if normalized.as_str() != human {
+            return Err(StdError::generic_err(
+                "Invalid input: address not normalized",
+            ));
+        }
+        Ok(Addr::unchecked(normalized.0))
+    }
 }
 
 /// A short-hand alias for the two-level query result (1. accessing the contract, 2. executing query in the contract)
@@ -152,6 +174,84 @@ pub trait Querier {
         Ok(res.amount)
     }
 
+    /// Queries another contract through its public query API. The message is serialized to JSON,
+    /// the response is parsed into whatever type the caller expects.
+    fn query_wasm_smart<T: DeserializeOwned, U: Into<HumanAddr>, M: Serialize>(
+        &self,
+        contract_addr: U,
+        callback_code_hash: &str,
+        msg: &M,
+    ) -> StdResult<T> {
+        let request = WasmQuery::Smart {
+            contract_addr: contract_addr.into(),
+            callback_code_hash: callback_code_hash.to_string(),
+            msg: to_vec(msg)?.into(),
+        }
+        .into();
+        self.query(&request)
+    }
+
+    /// Reads the raw value stored at `key` by another contract. The value is not parsed.
+    /// Returns None if the key does not exist, which the chain answers with an empty value.
+    fn query_wasm_raw<U: Into<HumanAddr>, K: Into<Binary>>(
+        &self,
+        contract_addr: U,
+        callback_code_hash: &str,
+        key: K,
+    ) -> StdResult<Option<Vec<u8>>> {
+        let request: QueryRequest<Empty> = WasmQuery::Raw {
+            contract_addr: contract_addr.into(),
+            key: key.into(),
+            callback_code_hash: callback_code_hash.to_string(),
+        }
+        .into();
+        let raw = match to_vec(&request) {
+            Ok(raw) => raw,
+            Err(e) => {
+                return Err(StdError::generic_err(format!(
+                    "Serializing QueryRequest: {}",
+                    e
+                )))
+            }
+        };
+        match self.raw_query(&raw) {
+            Err(sys) => Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                sys
+            ))),
+            Ok(Err(err)) => Err(err),
+            Ok(Ok(value)) if value.is_empty() => Ok(None),
+            // the value is returned as is, not as JSON
+            Ok(Ok(value)) => Ok(Some(value.into())),
+        }
+    }
+
+    fn query_rewards<U: Into<HumanAddr>>(&self, delegator: U) -> StdResult<RewardsResponse> {
+        let request = DistQuery::Rewards {
+            delegator: delegator.into(),
+        }
+        .into();
+        self.query(&request)
+    }
+
+    fn query_inflation(&self) -> StdResult<String> {
+        let request = MintQuery::Inflation {}.into();
+        let res: InflationResponse = self.query(&request)?;
+        Ok(res.inflation_rate)
+    }
+
+    fn query_bonded_ratio(&self) -> StdResult<String> {
+        let request = MintQuery::BondedRatio {}.into();
+        let res: BondedRatioResponse = self.query(&request)?;
+        Ok(res.bonded_ratio)
+    }
+
+    fn query_proposals(&self) -> StdResult<Vec<Proposal>> {
+        let request = GovQuery::Proposals {}.into();
+        let res: ProposalsResponse = self.query(&request)?;
+        Ok(res.proposals)
+    }
+
     #[cfg(feature = "staking")]
     fn query_validators(&self) -> StdResult<Vec<Validator>> {
         let request = StakingQuery::Validators {}.into();
@@ -193,4 +293,17 @@ pub trait Querier {
         let res: DelegationResponse = self.query(&request)?;
         Ok(res.delegation)
     }
+
+    #[cfg(feature = "staking")]
+    fn query_unbonding_delegations<U: Into<HumanAddr>>(
+        &self,
+        delegator: U,
+    ) -> StdResult<Vec<Delegation>> {
+        let request = StakingQuery::UnbondingDelegations {
+            delegator: delegator.into(),
+        }
+        .into();
+        let res: UnbondingDelegationsResponse = self.query(&request)?;
+        Ok(res.delegations)
+    }
 }
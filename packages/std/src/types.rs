@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 use crate::addresses::HumanAddr;
-use crate::coins::Coin;
+use crate::coins::{Coin, Coins};
+use crate::errors::StdResult;
+use crate::timestamp::Timestamp;
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Env {
     pub block: BlockInfo,
     pub message: MessageInfo,
@@ -14,6 +18,7 @@ pub struct Env {
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BlockInfo {
     pub height: u64,
     // time is seconds since epoch begin (Jan. 1, 1970)
@@ -21,7 +26,15 @@ pub struct BlockInfo {
     pub chain_id: String,
 }
 
+impl BlockInfo {
+    /// Returns the block time as a Timestamp
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp::from_seconds(self.time)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MessageInfo {
     /// The `sender` field from the wasm/MsgStoreCode, wasm/MsgInstantiateContract or wasm/MsgExecuteContract message.
     /// You can think of this as the address that initiated the action (i.e. the message). What that
@@ -37,7 +50,15 @@ pub struct MessageInfo {
     pub sent_funds: Vec<Coin>,
 }
 
+impl MessageInfo {
+    /// Returns the sent funds as a normalized Coins set, fails if a denom was sent twice
+    pub fn sent_coins(&self) -> StdResult<Coins> {
+        Coins::try_from(self.sent_funds.as_slice())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContractInfo {
     pub address: HumanAddr,
 }
@@ -49,6 +70,7 @@ pub struct ContractInfo {
 /// contains no meaningful data. Previously we used enums without cases,
 /// but those cannot represented as valid JSON Schema (https://github.com/CosmWasm/cosmwasm/issues/451)
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Empty {}
 
 #[cfg(test)]
@@ -69,4 +91,13 @@ mod test {
         let deserialized: Empty = from_slice(b"{\"stray\":\"data\"}").unwrap();
         assert_eq!(deserialized, instance);
     }
+
+    #[test]
+    fn sent_coins_works() {
+        let info = MessageInfo {
+            sender: HumanAddr::from("sender"),
+            sent_funds: vec![Coin::new(5, "ETH"), Coin::new(0, "BTC")],
+        };
+        assert_eq!(info.sent_coins().unwrap(), Coins::from(Coin::new(5, "ETH")));
+    }
 }
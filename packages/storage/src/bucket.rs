@@ -1,7 +1,7 @@
 use serde::{de::DeserializeOwned, ser::Serialize};
 use std::marker::PhantomData;
 
-use wasm2_std::{to_vec, ReadonlyStorage, StdResult, Storage};
+use wasm2_std::{Codec, Json, ReadonlyStorage, StdResult, Storage};
 #[cfg(feature = "iterator")]
 use wasm2_std::{Order, KV};
 
@@ -30,17 +30,21 @@ where
     ReadonlyBucket::new(namespace, storage)
 }
 
-pub struct Bucket<'a, S: Storage, T>
+/// Bucket stores values of type T under keys in a namespace. The values are encoded with
+/// the codec C, JSON by default, e.g. `Bucket::<_, Data, Bincode>::new` stores them in binary
+/// (with the `bincode` feature of wasm2-std).
+pub struct Bucket<'a, S: Storage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
     storage: &'a mut S,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
     prefix: Vec<u8>,
 }
 
-impl<'a, S: Storage, T> Bucket<'a, S, T>
+impl<'a, S: Storage, T, C: Codec> Bucket<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -49,6 +53,7 @@ where
             prefix: to_length_prefixed(namespace),
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
@@ -57,12 +62,13 @@ where
             prefix: to_length_prefixed_nested(namespaces),
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// save will serialize the model and store, returns an error on serialization issues
     pub fn save(&mut self, key: &[u8], data: &T) -> StdResult<()> {
-        set_with_prefix(self.storage, &self.prefix, key, &to_vec(data)?);
+        set_with_prefix(self.storage, &self.prefix, key, &C::encode(data)?);
         Ok(())
     }
 
@@ -73,14 +79,14 @@ where
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 
     #[cfg(feature = "iterator")]
@@ -91,7 +97,7 @@ where
         order: Order,
     ) -> Box<dyn Iterator<Item = StdResult<KV<T>>> + 'b> {
         let mapped = range_with_prefix(self.storage, &self.prefix, start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(deserialize_kv::<C, T>);
         Box::new(mapped)
     }
 
@@ -112,17 +118,18 @@ where
     }
 }
 
-pub struct ReadonlyBucket<'a, S: ReadonlyStorage, T>
+pub struct ReadonlyBucket<'a, S: ReadonlyStorage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
     storage: &'a S,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
     prefix: Vec<u8>,
 }
 
-impl<'a, S: ReadonlyStorage, T> ReadonlyBucket<'a, S, T>
+impl<'a, S: ReadonlyStorage, T, C: Codec> ReadonlyBucket<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -131,6 +138,7 @@ where
             prefix: to_length_prefixed(namespace),
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
@@ -139,20 +147,21 @@ where
             prefix: to_length_prefixed_nested(namespaces),
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = get_with_prefix(self.storage, &self.prefix, key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 
     #[cfg(feature = "iterator")]
@@ -163,7 +172,7 @@ where
         order: Order,
     ) -> Box<dyn Iterator<Item = StdResult<KV<T>>> + 'b> {
         let mapped = range_with_prefix(self.storage, &self.prefix, start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(deserialize_kv::<C, T>);
         Box::new(mapped)
     }
 }
@@ -172,7 +181,7 @@ where
 mod test {
     use super::*;
     use wasm2_std::testing::MockStorage;
-    use wasm2_std::StdError;
+    use wasm2_std::{Bincode, StdError};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
@@ -181,6 +190,25 @@ mod test {
         pub age: i32,
     }
 
+    #[test]
+    fn store_and_load_with_codec() {
+        let mut store = MockStorage::new();
+        let mut bucket = Bucket::<_, Data, Bincode>::new(b"data", &mut store);
+
+        let data = Data {
+            name: "Maria".to_string(),
+            age: 42,
+        };
+        bucket.save(b"maria", &data).unwrap();
+        assert_eq!(data, bucket.load(b"maria").unwrap());
+
+        // the value is not JSON, it can only be read with the same codec
+        let reader = bucket_read::<_, Data>(b"data", &store);
+        assert!(reader.load(b"maria").is_err());
+        let reader = ReadonlyBucket::<_, Data, Bincode>::new(b"data", &store);
+        assert_eq!(data, reader.load(b"maria").unwrap());
+    }
+
     #[test]
     fn store_and_load() {
         let mut store = MockStorage::new();
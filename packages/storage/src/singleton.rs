@@ -1,7 +1,7 @@
 use serde::{de::DeserializeOwned, ser::Serialize};
 use std::marker::PhantomData;
 
-use wasm2_std::{to_vec, ReadonlyStorage, StdResult, Storage};
+use wasm2_std::{Codec, Json, ReadonlyStorage, StdResult, Storage};
 
 use crate::length_prefixed::to_length_prefixed;
 use crate::type_helpers::{may_deserialize, must_deserialize};
@@ -29,7 +29,9 @@ where
 /// work on a single storage key. It performs the to_length_prefixed transformation
 /// on the given name to ensure no collisions, and then provides the standard
 /// TypedStorage accessors, without requiring a key (which is defined in the constructor)
-pub struct Singleton<'a, S: Storage, T>
+///
+/// The value is encoded with the codec C, JSON by default.
+pub struct Singleton<'a, S: Storage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -37,9 +39,10 @@ where
     key: Vec<u8>,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
 }
 
-impl<'a, S: Storage, T> Singleton<'a, S, T>
+impl<'a, S: Storage, T, C: Codec> Singleton<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -48,12 +51,13 @@ where
             storage,
             key: to_length_prefixed(key),
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// save will serialize the model and store, returns an error on serialization issues
     pub fn save(&mut self, data: &T) -> StdResult<()> {
-        self.storage.set(&self.key, &to_vec(data)?);
+        self.storage.set(&self.key, &C::encode(data)?);
         Ok(())
     }
 
@@ -64,14 +68,14 @@ where
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self) -> StdResult<T> {
         let value = self.storage.get(&self.key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self) -> StdResult<Option<T>> {
         let value = self.storage.get(&self.key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 
     /// update will load the data, perform the specified action, and store the result
@@ -91,7 +95,7 @@ where
 
 /// ReadonlySingleton only requires a ReadonlyStorage and exposes only the
 /// methods of Singleton that don't modify state.
-pub struct ReadonlySingleton<'a, S: ReadonlyStorage, T>
+pub struct ReadonlySingleton<'a, S: ReadonlyStorage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -99,9 +103,10 @@ where
     key: Vec<u8>,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
 }
 
-impl<'a, S: ReadonlyStorage, T> ReadonlySingleton<'a, S, T>
+impl<'a, S: ReadonlyStorage, T, C: Codec> ReadonlySingleton<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -110,20 +115,21 @@ where
             storage,
             key: to_length_prefixed(key),
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self) -> StdResult<T> {
         let value = self.storage.get(&self.key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self) -> StdResult<Option<T>> {
         let value = self.storage.get(&self.key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 }
 
@@ -133,7 +139,7 @@ mod test {
     use wasm2_std::testing::MockStorage;
     use serde::{Deserialize, Serialize};
 
-    use wasm2_std::StdError;
+    use wasm2_std::{Bincode, StdError};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
     struct Config {
@@ -141,6 +147,22 @@ mod test {
         pub max_tokens: i32,
     }
 
+    #[test]
+    fn save_and_load_with_codec() {
+        let mut store = MockStorage::new();
+        let mut single = Singleton::<_, Config, Bincode>::new(&mut store, b"config");
+
+        let cfg = Config {
+            owner: "admin".to_string(),
+            max_tokens: 1234,
+        };
+        single.save(&cfg).unwrap();
+        assert_eq!(cfg, single.load().unwrap());
+
+        let reader = ReadonlySingleton::<_, Config, Bincode>::new(&store, b"config");
+        assert_eq!(cfg, reader.load().unwrap());
+    }
+
     #[test]
     fn save_and_load() {
         let mut store = MockStorage::new();
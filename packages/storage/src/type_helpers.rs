@@ -3,40 +3,42 @@ use std::any::type_name;
 
 #[cfg(feature = "iterator")]
 use wasm2_std::KV;
-use wasm2_std::{from_slice, StdError, StdResult};
+use wasm2_std::{Codec, StdError, StdResult};
 
-/// may_deserialize parses json bytes from storage (Option), returning Ok(None) if no data present
+/// may_deserialize decodes bytes from storage (Option) with the codec C, returning Ok(None) if no data present
 ///
 /// value is an odd type, but this is meant to be easy to use with output from storage.get (Option<Vec<u8>>)
 /// and value.map(|s| s.as_slice()) seems trickier than &value
-pub(crate) fn may_deserialize<T: DeserializeOwned>(
+pub(crate) fn may_deserialize<C: Codec, T: DeserializeOwned>(
     value: &Option<Vec<u8>>,
 ) -> StdResult<Option<T>> {
     match value {
-        Some(vec) => Ok(Some(from_slice(&vec)?)),
+        Some(vec) => Ok(Some(C::decode(&vec)?)),
         None => Ok(None),
     }
 }
 
-/// must_deserialize parses json bytes from storage (Option), returning NotFound error if no data present
-pub(crate) fn must_deserialize<T: DeserializeOwned>(value: &Option<Vec<u8>>) -> StdResult<T> {
+/// must_deserialize decodes bytes from storage (Option) with the codec C, returning NotFound error if no data present
+pub(crate) fn must_deserialize<C: Codec, T: DeserializeOwned>(
+    value: &Option<Vec<u8>>,
+) -> StdResult<T> {
     match value {
-        Some(vec) => from_slice(&vec),
+        Some(vec) => C::decode(&vec),
         None => Err(StdError::not_found(type_name::<T>())),
     }
 }
 
 #[cfg(feature = "iterator")]
-pub(crate) fn deserialize_kv<T: DeserializeOwned>(kv: KV) -> StdResult<KV<T>> {
+pub(crate) fn deserialize_kv<C: Codec, T: DeserializeOwned>(kv: KV) -> StdResult<KV<T>> {
     let (k, v) = kv;
-    let t = from_slice::<T>(&v)?;
+    let t = C::decode::<T>(&v)?;
     Ok((k, t))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use wasm2_std::{to_vec, StdError};
+    use wasm2_std::{to_vec, Json, StdError};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
@@ -53,13 +55,13 @@ mod test {
         };
         let value = to_vec(&person).unwrap();
 
-        let may_parse: Option<Person> = may_deserialize(&Some(value)).unwrap();
+        let may_parse: Option<Person> = may_deserialize::<Json, _>(&Some(value)).unwrap();
         assert_eq!(may_parse, Some(person));
     }
 
     #[test]
     fn may_deserialize_handles_none() {
-        let may_parse = may_deserialize::<Person>(&None).unwrap();
+        let may_parse = may_deserialize::<Json, Person>(&None).unwrap();
         assert_eq!(may_parse, None);
     }
 
@@ -72,13 +74,13 @@ mod test {
         let value = to_vec(&person).unwrap();
         let loaded = Some(value);
 
-        let parsed: Person = must_deserialize(&loaded).unwrap();
+        let parsed: Person = must_deserialize::<Json, _>(&loaded).unwrap();
         assert_eq!(parsed, person);
     }
 
     #[test]
     fn must_deserialize_handles_none() {
-        let parsed = must_deserialize::<Person>(&None);
+        let parsed = must_deserialize::<Json, Person>(&None);
         match parsed.unwrap_err() {
             StdError::NotFound { kind, .. } => {
                 assert_eq!(kind, "cosmwasm_storage::type_helpers::test::Person")
@@ -1,7 +1,7 @@
 use serde::{de::DeserializeOwned, ser::Serialize};
 use std::marker::PhantomData;
 
-use wasm2_std::{to_vec, ReadonlyStorage, StdResult, Storage};
+use wasm2_std::{Codec, Json, ReadonlyStorage, StdResult, Storage};
 #[cfg(feature = "iterator")]
 use wasm2_std::{Order, KV};
 
@@ -23,16 +23,19 @@ where
     ReadonlyTypedStorage::new(storage)
 }
 
-pub struct TypedStorage<'a, S: Storage, T>
+/// TypedStorage stores values of type T directly under their keys, encoded with the codec C
+/// (JSON by default)
+pub struct TypedStorage<'a, S: Storage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
     storage: &'a mut S,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
 }
 
-impl<'a, S: Storage, T> TypedStorage<'a, S, T>
+impl<'a, S: Storage, T, C: Codec> TypedStorage<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -40,26 +43,27 @@ where
         TypedStorage {
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// save will serialize the model and store, returns an error on serialization issues
     pub fn save(&mut self, key: &[u8], data: &T) -> StdResult<()> {
-        self.storage.set(key, &to_vec(data)?);
+        self.storage.set(key, &C::encode(data)?);
         Ok(())
     }
 
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = self.storage.get(key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = self.storage.get(key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 
     #[cfg(feature = "iterator")]
@@ -72,7 +76,7 @@ where
         let mapped = self
             .storage
             .range(start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(deserialize_kv::<C, T>);
         Box::new(mapped)
     }
 
@@ -91,16 +95,17 @@ where
     }
 }
 
-pub struct ReadonlyTypedStorage<'a, S: ReadonlyStorage, T>
+pub struct ReadonlyTypedStorage<'a, S: ReadonlyStorage, T, C: Codec = Json>
 where
     T: Serialize + DeserializeOwned,
 {
     storage: &'a S,
     // see https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-type-parameters for why this is needed
     data: PhantomData<&'a T>,
+    codec: PhantomData<C>,
 }
 
-impl<'a, S: ReadonlyStorage, T> ReadonlyTypedStorage<'a, S, T>
+impl<'a, S: ReadonlyStorage, T, C: Codec> ReadonlyTypedStorage<'a, S, T, C>
 where
     T: Serialize + DeserializeOwned,
 {
@@ -108,20 +113,21 @@ where
         ReadonlyTypedStorage {
             storage,
             data: PhantomData,
+            codec: PhantomData,
         }
     }
 
     /// load will return an error if no data is set at the given key, or on parse error
     pub fn load(&self, key: &[u8]) -> StdResult<T> {
         let value = self.storage.get(key);
-        must_deserialize(&value)
+        must_deserialize::<C, T>(&value)
     }
 
     /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
     /// returns an error on issues parsing
     pub fn may_load(&self, key: &[u8]) -> StdResult<Option<T>> {
         let value = self.storage.get(key);
-        may_deserialize(&value)
+        may_deserialize::<C, T>(&value)
     }
 
     #[cfg(feature = "iterator")]
@@ -134,7 +140,7 @@ where
         let mapped = self
             .storage
             .range(start, end, order)
-            .map(deserialize_kv::<T>);
+            .map(deserialize_kv::<C, T>);
         Box::new(mapped)
     }
 }